@@ -367,9 +367,9 @@ mod internal {
     #[allow(non_upper_case_globals)]
     fn raw_fcc_to_frameformat(raw: OSType) -> Option<FrameFormat> {
         match raw {
-            kCMVideoCodecType_422YpCbCr8 | kCMPixelFormat_422YpCbCr8_yuvs => {
-                Some(FrameFormat::YUYV)
-            }
+            // '2vuy' is Cb Y0 Cr Y1, 'yuvs' is Y0 Cb Y1 Cr
+            kCMVideoCodecType_422YpCbCr8 => Some(FrameFormat::UYVY),
+            kCMPixelFormat_422YpCbCr8_yuvs => Some(FrameFormat::YUYV),
             kCMVideoCodecType_JPEG | kCMVideoCodecType_JPEG_OpenDML => Some(FrameFormat::MJPEG),
             kCMPixelFormat_8IndexedGray_WhiteIsZero => Some(FrameFormat::GRAY),
             kCVPixelFormatType_420YpCbCr10BiPlanarVideoRange
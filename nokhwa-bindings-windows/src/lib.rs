@@ -110,12 +110,42 @@ pub mod wmf {
         0x0010,
         [0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71],
     );
+    const MF_VIDEO_FORMAT_L16: GUID = GUID::from_values(
+        0x0000_0051,
+        0x0000,
+        0x0010,
+        [0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71],
+    );
     const MF_VIDEO_FORMAT_NV12: GUID = GUID::from_values(
         0x3231_564E,
         0x0000,
         0x0010,
         [0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71],
     );
+    const MF_VIDEO_FORMAT_UYVY: GUID = GUID::from_values(
+        0x5956_5955,
+        0x0000,
+        0x0010,
+        [0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71],
+    );
+    const MF_VIDEO_FORMAT_I420: GUID = GUID::from_values(
+        0x3032_3449,
+        0x0000,
+        0x0010,
+        [0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71],
+    );
+    const MF_VIDEO_FORMAT_H264: GUID = GUID::from_values(
+        0x3436_3248,
+        0x0000,
+        0x0010,
+        [0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71],
+    );
+    const MF_VIDEO_FORMAT_HEVC: GUID = GUID::from_values(
+        0x4356_4548,
+        0x0000,
+        0x0010,
+        [0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71],
+    );
     const MF_VIDEO_FORMAT_RGB24: GUID = GUID::from_values(
         0x0000_0014,
         0x0000,
@@ -170,22 +200,44 @@ pub mod wmf {
     fn guid_to_frameformat(guid: GUID) -> Option<FrameFormat> {
         match guid {
             MF_VIDEO_FORMAT_NV12 => Some(FrameFormat::NV12),
-            MF_VIDEO_FORMAT_RGB24 => Some(FrameFormat::RAWRGB),
+            MF_VIDEO_FORMAT_I420 => Some(FrameFormat::I420),
+            MF_VIDEO_FORMAT_UYVY => Some(FrameFormat::UYVY),
+            // Media Foundation's RGB24 is stored as BGR
+            MF_VIDEO_FORMAT_RGB24 => Some(FrameFormat::RAWBGR),
             MF_VIDEO_FORMAT_GRAY => Some(FrameFormat::GRAY),
+            MF_VIDEO_FORMAT_L16 => Some(FrameFormat::GRAY16LE),
             MF_VIDEO_FORMAT_YUY2 => Some(FrameFormat::YUYV),
             MF_VIDEO_FORMAT_MJPEG => Some(FrameFormat::MJPEG),
+            MF_VIDEO_FORMAT_H264 => Some(FrameFormat::H264),
+            MF_VIDEO_FORMAT_HEVC => Some(FrameFormat::H265),
             _ => None,
         }
     }
 
-    fn frameformat_to_guid(frameformat: FrameFormat) -> GUID {
-        match frameformat {
+    // Media Foundation has no RGB ordered 24-bit format, so `RAWRGB` has no subtype.
+    fn frameformat_to_guid(frameformat: FrameFormat) -> Option<GUID> {
+        let guid = match frameformat {
             FrameFormat::MJPEG => MF_VIDEO_FORMAT_MJPEG,
             FrameFormat::YUYV => MF_VIDEO_FORMAT_YUY2,
+            FrameFormat::UYVY => MF_VIDEO_FORMAT_UYVY,
             FrameFormat::NV12 => MF_VIDEO_FORMAT_NV12,
+            FrameFormat::I420 => MF_VIDEO_FORMAT_I420,
             FrameFormat::GRAY => MF_VIDEO_FORMAT_GRAY,
-            FrameFormat::RAWRGB => MF_VIDEO_FORMAT_RGB24,
-        }
+            FrameFormat::H264 => MF_VIDEO_FORMAT_H264,
+            FrameFormat::H265 => MF_VIDEO_FORMAT_HEVC,
+            // Media Foundation has no big endian 16-bit gray format, so it is never enumerated.
+            FrameFormat::GRAY16LE | FrameFormat::GRAY16BE => MF_VIDEO_FORMAT_L16,
+            FrameFormat::RAWBGR => MF_VIDEO_FORMAT_RGB24,
+            FrameFormat::RAWRGB => return None,
+            // Media Foundation's subtypes are the FourCC followed by the same fixed suffix as above
+            FrameFormat::Custom(fourcc) => GUID::from_values(
+                u32::from_le_bytes(fourcc),
+                0x0000,
+                0x0010,
+                [0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71],
+            ),
+        };
+        Some(guid)
     }
 
     pub fn initialize_mf() -> Result<(), NokhwaError> {
@@ -1033,7 +1085,16 @@ pub mod wmf {
                 println!("{:?}", bytes);
                 u64::from_le_bytes(bytes)
             };
-            let fourcc = frameformat_to_guid(format.format());
+            let fourcc = match frameformat_to_guid(format.format()) {
+                Some(guid) => guid,
+                None => {
+                    return Err(NokhwaError::SetPropertyError {
+                        property: "MF_MT_SUBTYPE".to_string(),
+                        value: format.format().to_string(),
+                        error: "FrameFormat not supported by Media Foundation".to_string(),
+                    })
+                }
+            };
             // setting to the new media_type
             if let Err(why) = unsafe { media_type.SetGUID(&MF_MT_MAJOR_TYPE, &MFMediaType_Video) } {
                 return Err(NokhwaError::SetPropertyError {
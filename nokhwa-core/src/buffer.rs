@@ -14,34 +14,74 @@
  * limitations under the License.
  */
 
+#[cfg(feature = "ndarray-types")]
+use crate::pixel_format::LumaFormat;
 use crate::{
     error::NokhwaError,
-    pixel_format::FormatDecoder,
-    types::{FrameFormat, Resolution},
+    pixel_format::{FormatDecoder, RgbFormat},
+    types::{mjpeg_to_rgb_scaled, Endianness, FrameFormat, RegionOfInterest, Resolution},
 };
 use bytes::Bytes;
-use image::ImageBuffer;
+use image::{ImageBuffer, Rgb};
+#[cfg(feature = "ndarray-types")]
+use ndarray::Array3;
+use std::time::Duration;
 
 /// A buffer returned by a camera to accomodate custom decoding.
 /// Contains information of Resolution, the buffer's [`FrameFormat`], and the buffer.
+/// Backends that report them also attach the driver's capture timestamp and sequence number of the frame.
 #[derive(Clone, Debug, Hash, PartialOrd, PartialEq, Eq)]
 pub struct Buffer {
     resolution: Resolution,
     buffer: Bytes,
     source_frame_format: FrameFormat,
+    timestamp: Option<Duration>,
+    sequence: Option<u32>,
 }
 
 impl Buffer {
     /// Creates a new buffer with a [`&[u8]`].
+    ///
+    /// This copies `buf`, so the returned [`Buffer`] owns its data and does not borrow from e.g. a driver-mapped frame.
     #[must_use]
     pub fn new(res: Resolution, buf: &[u8], source_frame_format: FrameFormat) -> Self {
         Self {
             resolution: res,
             buffer: Bytes::copy_from_slice(buf),
             source_frame_format,
+            timestamp: None,
+            sequence: None,
         }
     }
 
+    /// Sets the capture timestamp of this buffer, see [`timestamp()`](Self::timestamp).
+    #[must_use]
+    pub fn with_timestamp(mut self, timestamp: Option<Duration>) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Sets the sequence number of this buffer, see [`sequence()`](Self::sequence).
+    #[must_use]
+    pub fn with_sequence(mut self, sequence: Option<u32>) -> Self {
+        self.sequence = sequence;
+        self
+    }
+
+    /// Get the driver's capture timestamp of this frame, if the backend reports it. The clock is backend specific (for V4L2, usually `CLOCK_MONOTONIC`),
+    /// so only differences between timestamps are meaningful, e.g. for A/V sync.
+    #[must_use]
+    pub fn timestamp(&self) -> Option<Duration> {
+        self.timestamp
+    }
+
+    /// Get the driver's sequence number of this frame, if the backend reports it. It counts every frame the driver captured,
+    /// so a gap between consecutive buffers means frames were dropped.
+    #[must_use]
+    pub fn sequence(&self) -> Option<u32> {
+        self.sequence
+    }
+
     /// Get the [`Resolution`] of this buffer.
     #[must_use]
     pub fn resolution(&self) -> Resolution {
@@ -66,6 +106,312 @@ impl Buffer {
         self.source_frame_format
     }
 
+    /// Iterates over the 16-bit samples of a [`FrameFormat::GRAY16LE`] or [`FrameFormat::GRAY16BE`] buffer, in native `u16`s regardless of the device's byte order.
+    ///
+    /// Returns `None` for formats that are not 16-bit (see [`FrameFormat::endianness()`]), or if the buffer is too short for its resolution.
+    #[must_use]
+    pub fn u16_samples(&self) -> Option<impl Iterator<Item = u16> + '_> {
+        let endianness = self.source_frame_format.endianness()?;
+        let pixels = self.resolution.width() as usize * self.resolution.height() as usize;
+        Some(
+            self.buffer
+                .get(..pixels * 2)?
+                .chunks_exact(2)
+                .map(move |sample| match endianness {
+                    Endianness::Little => u16::from_le_bytes([sample[0], sample[1]]),
+                    Endianness::Big => u16::from_be_bytes([sample[0], sample[1]]),
+                }),
+        )
+    }
+
+    /// Computes a fast, non-cryptographic 64-bit hash (FNV-1a) of the raw frame bytes.
+    ///
+    /// Identical frames always produce the same hash, so this can be used to detect stuck streams (the same hash repeating) or to skip duplicate frames.
+    /// The result is stable across runs and platforms. The [`Resolution`] and [`FrameFormat`] are not part of the hash.
+    #[must_use]
+    pub fn hash(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0100_0000_01b3;
+        self.buffer.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+        })
+    }
+
+    /// Returns true if the frame is (nearly) a single solid color, e.g. black from a covered lens or green from a camera that failed to initialize.
+    ///
+    /// The frame is decoded to RGB and up to 4096 evenly spaced pixels are sampled. It is blank if the variance of every color channel
+    /// is below `threshold` (in squared 8-bit levels, so `1.0` is a deviation of about one level). Frames that fail to decode are not blank.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn is_blank(&self, threshold: f32) -> bool {
+        const MAX_SAMPLES: usize = 4096;
+        let Ok(rgb) =
+            RgbFormat::write_output(self.source_frame_format, self.resolution, &self.buffer)
+        else {
+            return false;
+        };
+        let pixels = rgb.len() / 3;
+        if pixels == 0 {
+            return false;
+        }
+        let step = (pixels / MAX_SAMPLES).max(1);
+
+        let (mut sum, mut sum_squares, mut count) = ([0_f64; 3], [0_f64; 3], 0_f64);
+        for pixel in rgb.chunks_exact(3).step_by(step) {
+            for (channel, value) in pixel.iter().enumerate() {
+                let value = f64::from(*value);
+                sum[channel] += value;
+                sum_squares[channel] += value * value;
+            }
+            count += 1.0;
+        }
+        (0..3).all(|channel| {
+            let mean = sum[channel] / count;
+            sum_squares[channel] / count - mean * mean < f64::from(threshold)
+        })
+    }
+
+    /// Iterates over the luma (Y) samples of this buffer without copying or converting.
+    ///
+    /// This works for [`FrameFormat::YUYV`] and [`FrameFormat::UYVY`] (every other byte), [`FrameFormat::NV12`] and [`FrameFormat::I420`] (the Y plane), and [`FrameFormat::GRAY`].
+    /// For [`FrameFormat::GRAY16LE`] and [`FrameFormat::GRAY16BE`], this yields the most significant byte of each sample; see [`u16_samples()`](Self::u16_samples) for full precision.
+    /// Returns `None` for formats where luma is not directly indexable ([`FrameFormat::MJPEG`], [`FrameFormat::RAWRGB`], [`FrameFormat::RAWBGR`], [`FrameFormat::H264`], [`FrameFormat::H265`], [`FrameFormat::Custom`]), or if the buffer is too short for its resolution.
+    #[must_use]
+    pub fn luma_iter(&self) -> Option<impl Iterator<Item = u8> + '_> {
+        let pixels = self.resolution.width() as usize * self.resolution.height() as usize;
+        let (offset, luma_bytes, stride) = match self.source_frame_format {
+            FrameFormat::YUYV | FrameFormat::GRAY16BE => (0, pixels * 2, 2),
+            FrameFormat::UYVY | FrameFormat::GRAY16LE => (1, pixels * 2, 2),
+            FrameFormat::NV12 | FrameFormat::I420 | FrameFormat::GRAY => (0, pixels, 1),
+            FrameFormat::MJPEG
+            | FrameFormat::RAWRGB
+            | FrameFormat::RAWBGR
+            | FrameFormat::H264
+            | FrameFormat::H265
+            | FrameFormat::Custom(_) => return None,
+        };
+        Some(
+            self.buffer
+                .get(offset..luma_bytes)?
+                .iter()
+                .step_by(stride)
+                .copied(),
+        )
+    }
+
+    /// Copies `region` out of this buffer into a new [`Buffer`] of the same [`FrameFormat`], without decoding.
+    ///
+    /// For [`FrameFormat::YUYV`] and [`FrameFormat::UYVY`], `x` and `width` must be even. For [`FrameFormat::NV12`] and [`FrameFormat::I420`], all of `x`, `y`, `width`, and `height` must be even.
+    /// # Errors
+    /// Will error for [`FrameFormat::MJPEG`], [`FrameFormat::H264`], [`FrameFormat::H265`] and [`FrameFormat::Custom`], if the region is empty, misaligned, or out of bounds, or if the buffer is too short for its resolution.
+    pub fn crop(&self, region: RegionOfInterest) -> Result<Buffer, NokhwaError> {
+        let crop_error = |error: String| NokhwaError::ProcessFrameError {
+            src: self.source_frame_format,
+            destination: format!("Crop {region}"),
+            error,
+        };
+        let frame_width = self.resolution.width() as usize;
+        let frame_height = self.resolution.height() as usize;
+        let (x, y) = (region.x() as usize, region.y() as usize);
+        let (width, height) = (region.width() as usize, region.height() as usize);
+        if width == 0 || height == 0 || x + width > frame_width || y + height > frame_height {
+            return Err(crop_error(format!(
+                "Region is empty or outside of {}",
+                self.resolution
+            )));
+        }
+
+        let bytes_per_pixel = match self.source_frame_format {
+            FrameFormat::MJPEG | FrameFormat::H264 | FrameFormat::H265 => {
+                return Err(crop_error(
+                    "Compressed frames must be decoded first".to_string(),
+                ))
+            }
+            FrameFormat::Custom(_) => {
+                return Err(crop_error(
+                    "Custom formats have an unknown layout".to_string(),
+                ))
+            }
+            FrameFormat::YUYV
+            | FrameFormat::UYVY
+            | FrameFormat::GRAY16LE
+            | FrameFormat::GRAY16BE => 2,
+            FrameFormat::NV12 | FrameFormat::I420 | FrameFormat::GRAY => 1,
+            FrameFormat::RAWRGB | FrameFormat::RAWBGR => 3,
+        };
+        let misaligned = match self.source_frame_format {
+            FrameFormat::YUYV | FrameFormat::UYVY => x % 2 != 0 || width % 2 != 0,
+            FrameFormat::NV12 | FrameFormat::I420 => {
+                x % 2 != 0 || y % 2 != 0 || width % 2 != 0 || height % 2 != 0
+            }
+            _ => false,
+        };
+        if misaligned {
+            return Err(crop_error(
+                "Region is not aligned to the chroma subsampling".to_string(),
+            ));
+        }
+
+        let mut cropped = Vec::with_capacity(width * height * bytes_per_pixel);
+        let mut copy_rows = |plane_offset: usize,
+                             row_stride: usize,
+                             rows: std::ops::Range<usize>,
+                             columns: std::ops::Range<usize>| {
+            for row in rows {
+                let start = plane_offset + row * row_stride;
+                let row_data = self
+                    .buffer
+                    .get(start + columns.start..start + columns.end)
+                    .ok_or_else(|| crop_error("Buffer too short for its resolution".to_string()))?;
+                cropped.extend_from_slice(row_data);
+            }
+            Ok::<(), NokhwaError>(())
+        };
+        let row_stride = frame_width * bytes_per_pixel;
+        copy_rows(
+            0,
+            row_stride,
+            y..y + height,
+            x * bytes_per_pixel..(x + width) * bytes_per_pixel,
+        )?;
+        let luma_bytes = frame_width * frame_height;
+        let chroma_rows = y / 2..y / 2 + height / 2;
+        match self.source_frame_format {
+            FrameFormat::NV12 => {
+                // interleaved UV plane, half height, same row length as the Y plane
+                copy_rows(luma_bytes, frame_width, chroma_rows, x..x + width)?;
+            }
+            FrameFormat::I420 => {
+                // separate U and V planes, each half width and half height
+                let chroma_columns = x / 2..x / 2 + width / 2;
+                let chroma_stride = frame_width.div_ceil(2);
+                let chroma_bytes = chroma_stride * frame_height.div_ceil(2);
+                copy_rows(
+                    luma_bytes,
+                    chroma_stride,
+                    chroma_rows.clone(),
+                    chroma_columns.clone(),
+                )?;
+                copy_rows(
+                    luma_bytes + chroma_bytes,
+                    chroma_stride,
+                    chroma_rows,
+                    chroma_columns,
+                )?;
+            }
+            _ => {}
+        }
+
+        Ok(Buffer {
+            resolution: region.resolution(),
+            buffer: Bytes::from(cropped),
+            source_frame_format: self.source_frame_format,
+            timestamp: self.timestamp,
+            sequence: self.sequence,
+        })
+    }
+
+    /// Decodes the frame into an [`Array3`] of shape `(height, width, channels)`, for numeric processing.
+    ///
+    /// Grayscale frames ([`FrameFormat::GRAY`], [`FrameFormat::GRAY16LE`], [`FrameFormat::GRAY16BE`]) are decoded to 8 bit luma with one channel, so
+    /// `.index_axis_move(Axis(2), 0)` gives a `(height, width)` array. Every other format is decoded to RGB with three channels.
+    /// # Errors
+    /// Will error when the decoding fails, or the decoded data does not match the resolution.
+    #[cfg(feature = "ndarray-types")]
+    #[cfg_attr(feature = "docs-features", doc(cfg(feature = "ndarray-types")))]
+    pub fn to_ndarray(&self) -> Result<Array3<u8>, NokhwaError> {
+        let (channels, data) = match self.source_frame_format {
+            FrameFormat::GRAY | FrameFormat::GRAY16LE | FrameFormat::GRAY16BE => (
+                1,
+                LumaFormat::write_output(self.source_frame_format, self.resolution, &self.buffer)?,
+            ),
+            _ => (
+                3,
+                RgbFormat::write_output(self.source_frame_format, self.resolution, &self.buffer)?,
+            ),
+        };
+        Array3::from_shape_vec(
+            (
+                self.resolution.height_y as usize,
+                self.resolution.width_x as usize,
+                channels,
+            ),
+            data,
+        )
+        .map_err(|why| NokhwaError::ProcessFrameError {
+            src: self.source_frame_format,
+            destination: "ndarray".to_string(),
+            error: why.to_string(),
+        })
+    }
+
+    /// Decodes a small RGB preview of the frame, at most `max_dim` pixels wide and high, keeping the aspect ratio.
+    ///
+    /// This is much cheaper than a full decode followed by a resize: [`FrameFormat::MJPEG`] frames are decoded directly at a reduced scale (down to 1/8),
+    /// and the rest is shrunk by averaging blocks of pixels (box filter). Frames that already fit are returned at their full size.
+    /// # Errors
+    /// Will error when the decoding fails, or `max_dim` is 0.
+    pub fn thumbnail(&self, max_dim: u32) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, NokhwaError> {
+        let thumbnail_error = |error: String| NokhwaError::ProcessFrameError {
+            src: self.source_frame_format,
+            destination: "RGB888 thumbnail".to_string(),
+            error,
+        };
+        if max_dim == 0 {
+            return Err(thumbnail_error("Thumbnail size must not be 0".to_string()));
+        }
+
+        let longest_side = self.resolution.width().max(self.resolution.height()).max(1);
+        let (resolution, rgb) = match self.source_frame_format {
+            FrameFormat::MJPEG => {
+                // the smallest scale (in eighths) that still covers `max_dim`, the rest is done by the box filter
+                let scale_num = (u64::from(max_dim) * 8).div_ceil(u64::from(longest_side));
+                mjpeg_to_rgb_scaled(&self.buffer, u8::try_from(scale_num.min(8)).unwrap_or(8))?
+            }
+            _ => (
+                self.resolution,
+                RgbFormat::write_output(self.source_frame_format, self.resolution, &self.buffer)?,
+            ),
+        };
+
+        let factor = resolution
+            .width()
+            .max(resolution.height())
+            .div_ceil(max_dim)
+            .max(1) as usize;
+        let (width, height) = (resolution.width() as usize, resolution.height() as usize);
+        let (thumb_width, thumb_height) = ((width / factor).max(1), (height / factor).max(1));
+        if rgb.len() < width * height * 3 {
+            return Err(thumbnail_error(
+                "Buffer too short for its resolution".to_string(),
+            ));
+        }
+
+        let mut thumbnail = Vec::with_capacity(thumb_width * thumb_height * 3);
+        for thumb_y in 0..thumb_height {
+            let rows = thumb_y * factor..((thumb_y + 1) * factor).min(height);
+            for thumb_x in 0..thumb_width {
+                let columns = thumb_x * factor..((thumb_x + 1) * factor).min(width);
+                let mut sum = [0_usize; 3];
+                for y in rows.clone() {
+                    for x in columns.clone() {
+                        let index = (y * width + x) * 3;
+                        for (channel, sum) in sum.iter_mut().enumerate() {
+                            *sum += usize::from(rgb[index + channel]);
+                        }
+                    }
+                }
+                let count = rows.len() * columns.len();
+                #[allow(clippy::cast_possible_truncation)]
+                thumbnail.extend(sum.iter().map(|sum| (sum / count) as u8));
+            }
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        ImageBuffer::from_raw(thumb_width as u32, thumb_height as u32, thumbnail)
+            .ok_or_else(|| thumbnail_error("Failed to create buffer".to_string()))
+    }
+
     /// Decodes a image with allocation using the provided [`FormatDecoder`].
     /// # Errors
     /// Will error when the decoding fails.
@@ -83,6 +429,124 @@ impl Buffer {
         Ok(image)
     }
 
+    /// Decodes the frame into tightly-packed RGB24 bytes, returned alongside its width and height.
+    ///
+    /// This avoids exposing [`image`] types, which is useful across FFI boundaries.
+    /// # Errors
+    /// Will error when the decoding fails, or the decoded data does not match the frame's resolution.
+    pub fn decode_rgb_raw(&self) -> Result<(Vec<u8>, u32, u32), NokhwaError> {
+        let width = self.resolution.width_x;
+        let height = self.resolution.height_y;
+        let data =
+            RgbFormat::write_output(self.source_frame_format, self.resolution, &self.buffer)?;
+        if data.len() != (width as usize) * (height as usize) * 3 {
+            return Err(NokhwaError::ProcessFrameError {
+                src: self.source_frame_format,
+                destination: "RGB24".to_string(),
+                error: format!(
+                    "Decoded {} bytes, expected {} for {}",
+                    data.len(),
+                    (width as usize) * (height as usize) * 3,
+                    self.resolution
+                ),
+            });
+        }
+        Ok((data, width, height))
+    }
+
+    /// Converts the frame into planar I420 (YUV 4:2:0): a full resolution Y plane followed by the U and V planes at half width and half height (rounded up).
+    ///
+    /// [`FrameFormat::YUYV`], [`FrameFormat::UYVY`], [`FrameFormat::NV12`], and [`FrameFormat::I420`] are repacked without leaving YUV. Other formats are decoded to RGB first and converted with full range BT.601 coefficients.
+    /// # Errors
+    /// Will error when the decoding fails, or the buffer is too short for its resolution.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn to_i420(&self) -> Result<Vec<u8>, NokhwaError> {
+        let width = self.resolution.width() as usize;
+        let height = self.resolution.height() as usize;
+        let (chroma_width, chroma_height) = (width.div_ceil(2), height.div_ceil(2));
+        let short_error = || NokhwaError::ProcessFrameError {
+            src: self.source_frame_format,
+            destination: "I420".to_string(),
+            error: "Buffer too short for its resolution".to_string(),
+        };
+
+        let mut y_plane = Vec::with_capacity(width * height);
+        let mut u_plane = Vec::with_capacity(chroma_width * chroma_height);
+        let mut v_plane = Vec::with_capacity(chroma_width * chroma_height);
+        match self.source_frame_format {
+            FrameFormat::YUYV | FrameFormat::UYVY => {
+                // byte offsets of Y, U, and V within each 4 byte macropixel
+                let (y, u, v) = if self.source_frame_format == FrameFormat::YUYV {
+                    (0, 1, 3)
+                } else {
+                    (1, 0, 2)
+                };
+                let row_bytes = width.div_ceil(2) * 4;
+                let data = self
+                    .buffer
+                    .get(..row_bytes * height)
+                    .ok_or_else(short_error)?;
+                for row in data.chunks_exact(row_bytes) {
+                    y_plane.extend(row.iter().skip(y).step_by(2).take(width));
+                }
+                // 4:2:2 to 4:2:0, averaging the chroma of each pair of rows
+                for row_pair in data.chunks(row_bytes * 2) {
+                    let (upper, lower) = row_pair.split_at(row_bytes.min(row_pair.len()));
+                    let lower = if lower.is_empty() { upper } else { lower };
+                    for (upper, lower) in upper.chunks_exact(4).zip(lower.chunks_exact(4)) {
+                        u_plane.push(u8::midpoint(upper[u], lower[u]));
+                        v_plane.push(u8::midpoint(upper[v], lower[v]));
+                    }
+                }
+            }
+            FrameFormat::NV12 => {
+                let uv_offset = width * height;
+                let data = self
+                    .buffer
+                    .get(..uv_offset + chroma_width * 2 * chroma_height)
+                    .ok_or_else(short_error)?;
+                y_plane.extend_from_slice(&data[..uv_offset]);
+                for uv in data[uv_offset..].chunks_exact(2) {
+                    u_plane.push(uv[0]);
+                    v_plane.push(uv[1]);
+                }
+            }
+            FrameFormat::I420 => {
+                let data = self
+                    .buffer
+                    .get(..width * height + chroma_width * chroma_height * 2)
+                    .ok_or_else(short_error)?;
+                return Ok(data.to_vec());
+            }
+            _ => {
+                let (rgb, _, _) = self.decode_rgb_raw()?;
+                let luma = |pixel: &[u8]| {
+                    (0.299 * f32::from(pixel[0])
+                        + 0.587 * f32::from(pixel[1])
+                        + 0.114 * f32::from(pixel[2])) as u8
+                };
+                y_plane.extend(rgb.chunks_exact(3).map(luma));
+                for chroma_y in 0..chroma_height {
+                    for chroma_x in 0..chroma_width {
+                        let pixel_start = (chroma_y * 2 * width + chroma_x * 2) * 3;
+                        let pixel = &rgb[pixel_start..pixel_start + 3];
+                        let (r, g, b) = (
+                            f32::from(pixel[0]),
+                            f32::from(pixel[1]),
+                            f32::from(pixel[2]),
+                        );
+                        u_plane.push((128.0 - 0.168_736 * r - 0.331_264 * g + 0.5 * b) as u8);
+                        v_plane.push((128.0 + 0.5 * r - 0.418_688 * g - 0.081_312 * b) as u8);
+                    }
+                }
+            }
+        }
+
+        y_plane.append(&mut u_plane);
+        y_plane.append(&mut v_plane);
+        Ok(y_plane)
+    }
+
     /// Decodes a image with allocation using the provided [`FormatDecoder`] into a `buffer`.
     /// # Errors
     /// Will error when the decoding fails, or the provided buffer is too small.
@@ -15,6 +15,7 @@
  */
 
 use crate::types::{ApiBackend, FrameFormat};
+use std::time::Duration;
 use thiserror::Error;
 
 /// All errors in `nokhwa`.
@@ -45,6 +46,10 @@ pub enum NokhwaError {
     OpenStreamError(String),
     #[error("Could not capture frame: {0}")]
     ReadFrameError(String),
+    #[error("No frame arrived within {0:?}")]
+    TimeoutError(Duration),
+    #[error("Device disconnected: {0}")]
+    DeviceDisconnected(String),
     #[error("Could not process frame {src} to {destination}: {error}")]
     ProcessFrameError {
         src: FrameFormat,
@@ -16,7 +16,7 @@
 use crate::error::NokhwaError;
 use crate::types::{
     buf_mjpeg_to_rgb, buf_yuv_420_to_rgb, buf_yuyv422_to_rgb, mjpeg_to_rgb, yuv_420_to_rgb,
-    yuyv422_to_rgb, FrameFormat, Resolution,
+    yuyv422_to_rgb, Endianness, FrameFormat, Resolution,
 };
 use image::{Luma, LumaA, Pixel, Rgb, Rgba};
 use std::fmt::Debug;
@@ -57,7 +57,13 @@ pub struct RgbFormat;
 
 impl FormatDecoder for RgbFormat {
     type Output = Rgb<u8>;
-    const FORMATS: &'static [FrameFormat] = &[FrameFormat::MJPEG, FrameFormat::YUYV];
+    const FORMATS: &'static [FrameFormat] = &[
+        FrameFormat::MJPEG,
+        FrameFormat::YUYV,
+        FrameFormat::UYVY,
+        FrameFormat::I420,
+        FrameFormat::RAWBGR,
+    ];
 
     fn write_output(
         fcc: FrameFormat,
@@ -65,6 +71,16 @@ impl FormatDecoder for RgbFormat {
         data: &[u8],
     ) -> Result<Vec<u8>, NokhwaError> {
         match fcc {
+            FrameFormat::H264 | FrameFormat::H265 | FrameFormat::Custom(_) => {
+                Err(undecodable_error(fcc, "RGB"))
+            }
+            FrameFormat::UYVY | FrameFormat::I420 | FrameFormat::RAWBGR => {
+                let (fcc, data) = repack_to_native(fcc, resolution, data);
+                Self::write_output(fcc, resolution, &data)
+            }
+            FrameFormat::GRAY16LE | FrameFormat::GRAY16BE => {
+                Self::write_output(FrameFormat::GRAY, resolution, &gray16_to_gray8(fcc, data))
+            }
             FrameFormat::MJPEG => mjpeg_to_rgb(data, false),
             FrameFormat::YUYV => yuyv422_to_rgb(data, false),
             FrameFormat::GRAY => Ok(data
@@ -86,6 +102,19 @@ impl FormatDecoder for RgbFormat {
         dest: &mut [u8],
     ) -> Result<(), NokhwaError> {
         match fcc {
+            FrameFormat::H264 | FrameFormat::H265 | FrameFormat::Custom(_) => {
+                Err(undecodable_error(fcc, "RGB"))
+            }
+            FrameFormat::UYVY | FrameFormat::I420 | FrameFormat::RAWBGR => {
+                let (fcc, data) = repack_to_native(fcc, resolution, data);
+                Self::write_output_buffer(fcc, resolution, &data, dest)
+            }
+            FrameFormat::GRAY16LE | FrameFormat::GRAY16BE => Self::write_output_buffer(
+                FrameFormat::GRAY,
+                resolution,
+                &gray16_to_gray8(fcc, data),
+                dest,
+            ),
             FrameFormat::MJPEG => buf_mjpeg_to_rgb(data, dest, false),
             FrameFormat::YUYV => buf_yuyv422_to_rgb(data, dest, false),
             FrameFormat::GRAY => {
@@ -126,7 +155,13 @@ pub struct RgbAFormat;
 impl FormatDecoder for RgbAFormat {
     type Output = Rgba<u8>;
 
-    const FORMATS: &'static [FrameFormat] = &[FrameFormat::MJPEG, FrameFormat::YUYV];
+    const FORMATS: &'static [FrameFormat] = &[
+        FrameFormat::MJPEG,
+        FrameFormat::YUYV,
+        FrameFormat::UYVY,
+        FrameFormat::I420,
+        FrameFormat::RAWBGR,
+    ];
 
     fn write_output(
         fcc: FrameFormat,
@@ -134,6 +169,16 @@ impl FormatDecoder for RgbAFormat {
         data: &[u8],
     ) -> Result<Vec<u8>, NokhwaError> {
         match fcc {
+            FrameFormat::H264 | FrameFormat::H265 | FrameFormat::Custom(_) => {
+                Err(undecodable_error(fcc, "RGBA"))
+            }
+            FrameFormat::UYVY | FrameFormat::I420 | FrameFormat::RAWBGR => {
+                let (fcc, data) = repack_to_native(fcc, resolution, data);
+                Self::write_output(fcc, resolution, &data)
+            }
+            FrameFormat::GRAY16LE | FrameFormat::GRAY16BE => {
+                Self::write_output(FrameFormat::GRAY, resolution, &gray16_to_gray8(fcc, data))
+            }
             FrameFormat::MJPEG => mjpeg_to_rgb(data, true),
             FrameFormat::YUYV => yuyv422_to_rgb(data, true),
             FrameFormat::GRAY => Ok(data
@@ -159,6 +204,19 @@ impl FormatDecoder for RgbAFormat {
         dest: &mut [u8],
     ) -> Result<(), NokhwaError> {
         match fcc {
+            FrameFormat::H264 | FrameFormat::H265 | FrameFormat::Custom(_) => {
+                Err(undecodable_error(fcc, "RGBA"))
+            }
+            FrameFormat::UYVY | FrameFormat::I420 | FrameFormat::RAWBGR => {
+                let (fcc, data) = repack_to_native(fcc, resolution, data);
+                Self::write_output_buffer(fcc, resolution, &data, dest)
+            }
+            FrameFormat::GRAY16LE | FrameFormat::GRAY16BE => Self::write_output_buffer(
+                FrameFormat::GRAY,
+                resolution,
+                &gray16_to_gray8(fcc, data),
+                dest,
+            ),
             FrameFormat::MJPEG => buf_mjpeg_to_rgb(data, dest, true),
             FrameFormat::YUYV => buf_yuyv422_to_rgb(data, dest, true),
             FrameFormat::GRAY => {
@@ -206,8 +264,13 @@ pub struct LumaFormat;
 impl FormatDecoder for LumaFormat {
     type Output = Luma<u8>;
 
-    const FORMATS: &'static [FrameFormat] =
-        &[FrameFormat::MJPEG, FrameFormat::YUYV, FrameFormat::GRAY];
+    const FORMATS: &'static [FrameFormat] = &[
+        FrameFormat::MJPEG,
+        FrameFormat::YUYV,
+        FrameFormat::GRAY,
+        FrameFormat::GRAY16LE,
+        FrameFormat::GRAY16BE,
+    ];
 
     #[allow(clippy::cast_possible_truncation)]
     #[allow(clippy::cast_sign_loss)]
@@ -217,6 +280,16 @@ impl FormatDecoder for LumaFormat {
         data: &[u8],
     ) -> Result<Vec<u8>, NokhwaError> {
         match fcc {
+            FrameFormat::H264 | FrameFormat::H265 | FrameFormat::Custom(_) => {
+                Err(undecodable_error(fcc, "Luma"))
+            }
+            FrameFormat::UYVY | FrameFormat::I420 | FrameFormat::RAWBGR => {
+                let (fcc, data) = repack_to_native(fcc, resolution, data);
+                Self::write_output(fcc, resolution, &data)
+            }
+            FrameFormat::GRAY16LE | FrameFormat::GRAY16BE => {
+                Self::write_output(FrameFormat::GRAY, resolution, &gray16_to_gray8(fcc, data))
+            }
             FrameFormat::MJPEG => Ok(mjpeg_to_rgb(data, false)?
                 .as_slice()
                 .chunks_exact(3)
@@ -254,11 +327,24 @@ impl FormatDecoder for LumaFormat {
 
     fn write_output_buffer(
         fcc: FrameFormat,
-        _resolution: Resolution,
+        resolution: Resolution,
         data: &[u8],
         dest: &mut [u8],
     ) -> Result<(), NokhwaError> {
         match fcc {
+            FrameFormat::H264 | FrameFormat::H265 | FrameFormat::Custom(_) => {
+                Err(undecodable_error(fcc, "Luma"))
+            }
+            FrameFormat::UYVY | FrameFormat::I420 | FrameFormat::RAWBGR => {
+                let (fcc, data) = repack_to_native(fcc, resolution, data);
+                Self::write_output_buffer(fcc, resolution, &data, dest)
+            }
+            FrameFormat::GRAY16LE | FrameFormat::GRAY16BE => Self::write_output_buffer(
+                FrameFormat::GRAY,
+                resolution,
+                &gray16_to_gray8(fcc, data),
+                dest,
+            ),
             // TODO: implement!
             FrameFormat::MJPEG | FrameFormat::YUYV | FrameFormat::NV12 => {
                 Err(NokhwaError::ProcessFrameError {
@@ -295,8 +381,13 @@ pub struct LumaAFormat;
 impl FormatDecoder for LumaAFormat {
     type Output = LumaA<u8>;
 
-    const FORMATS: &'static [FrameFormat] =
-        &[FrameFormat::MJPEG, FrameFormat::YUYV, FrameFormat::GRAY];
+    const FORMATS: &'static [FrameFormat] = &[
+        FrameFormat::MJPEG,
+        FrameFormat::YUYV,
+        FrameFormat::GRAY,
+        FrameFormat::GRAY16LE,
+        FrameFormat::GRAY16BE,
+    ];
 
     #[allow(clippy::cast_possible_truncation)]
     fn write_output(
@@ -305,6 +396,16 @@ impl FormatDecoder for LumaAFormat {
         data: &[u8],
     ) -> Result<Vec<u8>, NokhwaError> {
         match fcc {
+            FrameFormat::H264 | FrameFormat::H265 | FrameFormat::Custom(_) => {
+                Err(undecodable_error(fcc, "LumaA"))
+            }
+            FrameFormat::UYVY | FrameFormat::I420 | FrameFormat::RAWBGR => {
+                let (fcc, data) = repack_to_native(fcc, resolution, data);
+                Self::write_output(fcc, resolution, &data)
+            }
+            FrameFormat::GRAY16LE | FrameFormat::GRAY16BE => {
+                Self::write_output(FrameFormat::GRAY, resolution, &gray16_to_gray8(fcc, data))
+            }
             FrameFormat::MJPEG => Ok(mjpeg_to_rgb(data, false)?
                 .as_slice()
                 .chunks_exact(3)
@@ -343,11 +444,24 @@ impl FormatDecoder for LumaAFormat {
 
     fn write_output_buffer(
         fcc: FrameFormat,
-        _resolution: Resolution,
+        resolution: Resolution,
         data: &[u8],
         dest: &mut [u8],
     ) -> Result<(), NokhwaError> {
         match fcc {
+            FrameFormat::H264 | FrameFormat::H265 | FrameFormat::Custom(_) => {
+                Err(undecodable_error(fcc, "LumaA"))
+            }
+            FrameFormat::UYVY | FrameFormat::I420 | FrameFormat::RAWBGR => {
+                let (fcc, data) = repack_to_native(fcc, resolution, data);
+                Self::write_output_buffer(fcc, resolution, &data, dest)
+            }
+            FrameFormat::GRAY16LE | FrameFormat::GRAY16BE => Self::write_output_buffer(
+                FrameFormat::GRAY,
+                resolution,
+                &gray16_to_gray8(fcc, data),
+                dest,
+            ),
             FrameFormat::MJPEG => {
                 // FIXME: implement!
                 Err(NokhwaError::ProcessFrameError {
@@ -393,3 +507,57 @@ impl FormatDecoder for LumaAFormat {
         }
     }
 }
+
+// Repacks the formats that only differ in byte order or plane layout from one the decoders handle: UYVY to YUYV, I420 to NV12, and BGR to RGB.
+fn repack_to_native(
+    fcc: FrameFormat,
+    resolution: Resolution,
+    data: &[u8],
+) -> (FrameFormat, Vec<u8>) {
+    match fcc {
+        FrameFormat::UYVY => (
+            FrameFormat::YUYV,
+            data.chunks_exact(2)
+                .flat_map(|pair| [pair[1], pair[0]])
+                .collect(),
+        ),
+        FrameFormat::I420 => {
+            let luma_bytes = resolution.width() as usize * resolution.height() as usize;
+            let chroma_bytes =
+                resolution.width().div_ceil(2) as usize * resolution.height().div_ceil(2) as usize;
+            let (y_plane, chroma_planes) = data.split_at(luma_bytes.min(data.len()));
+            let (u_plane, v_plane) = chroma_planes.split_at(chroma_bytes.min(chroma_planes.len()));
+            let mut nv12 = Vec::with_capacity(luma_bytes + chroma_bytes * 2);
+            nv12.extend_from_slice(y_plane);
+            nv12.extend(u_plane.iter().zip(v_plane).flat_map(|(u, v)| [*u, *v]));
+            (FrameFormat::NV12, nv12)
+        }
+        FrameFormat::RAWBGR => (
+            FrameFormat::RAWRGB,
+            data.chunks_exact(3)
+                .flat_map(|pixel| [pixel[2], pixel[1], pixel[0]])
+                .collect(),
+        ),
+        _ => (fcc, data.to_vec()),
+    }
+}
+
+// The error for formats `nokhwa` cannot decode: compressed video, or `FrameFormat::Custom`, whose layout is unknown.
+fn undecodable_error(fcc: FrameFormat, destination: &str) -> NokhwaError {
+    NokhwaError::ProcessFrameError {
+        src: fcc,
+        destination: destination.to_string(),
+        error: format!("{fcc} frames are passed through undecoded"),
+    }
+}
+
+// Reduces 16-bit grayscale to 8-bit by keeping the most significant byte of each sample.
+fn gray16_to_gray8(fcc: FrameFormat, data: &[u8]) -> Vec<u8> {
+    let msb_index = match fcc.endianness() {
+        Some(Endianness::Little) => 1,
+        _ => 0,
+    };
+    data.chunks_exact(2)
+        .map(|sample| sample[msb_index])
+        .collect()
+}
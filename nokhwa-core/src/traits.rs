@@ -18,11 +18,13 @@ use crate::{
     buffer::Buffer,
     error::NokhwaError,
     types::{
-        ApiBackend, CameraControl, CameraFormat, CameraInfo, ControlValueSetter, FrameFormat,
-        KnownCameraControl, Resolution,
+        ApiBackend, CameraControl, CameraFormat, CameraInfo, Colorimetry, ControlDependency,
+        ControlNode, ControlValueSetter, FormatInfo, FormatNegotiation, FrameFormat,
+        FrameRateRange, KnownCameraControl, KnownCameraControlFlag, Resolution, RollingShutter,
+        UsbSpeed,
     },
 };
-use std::{borrow::Cow, collections::HashMap};
+use std::{borrow::Cow, collections::HashMap, time::Duration};
 #[cfg(feature = "wgpu-types")]
 use wgpu::{
     Device as WgpuDevice, Extent3d, ImageCopyTexture, ImageDataLayout, Queue as WgpuQueue,
@@ -30,6 +32,12 @@ use wgpu::{
     TextureUsages,
 };
 
+/// The controls that could be read, and the controls that failed to be read with the reason why, as returned by [`camera_controls_partial()`](CaptureBackendTrait::camera_controls_partial).
+pub type PartialCameraControls = (Vec<CameraControl>, Vec<(KnownCameraControl, NokhwaError)>);
+
+/// The result of setting each control, in the order they were given to [`set_camera_controls()`](CaptureBackendTrait::set_camera_controls).
+pub type CameraControlResults = Vec<(KnownCameraControl, Result<(), NokhwaError>)>;
+
 /// This trait is for any backend that allows you to grab and take frames from a camera.
 /// Many of the backends are **blocking**, if the camera is occupied the library will block while it waits for it to become available.
 ///
@@ -60,6 +68,14 @@ pub trait CaptureBackendTrait {
     /// If you started the stream and the camera rejects the new camera format, this will return an error.
     fn set_camera_format(&mut self, new_fmt: CameraFormat) -> Result<(), NokhwaError>;
 
+    /// Returns true if changing to `new_fmt` with [`set_camera_format()`](CaptureBackendTrait::set_camera_format) would interrupt the open stream (i.e. it has to be torn down and reopened).
+    ///
+    /// This is false if no stream is open or `new_fmt` is the current format. By default, any other change is considered disruptive.
+    #[must_use]
+    fn format_change_disruptive(&self, new_fmt: CameraFormat) -> bool {
+        self.is_stream_open() && new_fmt != self.camera_format()
+    }
+
     /// A hashmap of [`Resolution`]s mapped to framerates. Not sorted!
     /// # Errors
     /// This will error if the camera is not queryable or a query operation has failed. Some backends will error this out as a Unsupported Operation ([`UnsupportedOperationError`](crate::error::NokhwaError::UnsupportedOperationError)).
@@ -84,11 +100,45 @@ pub trait CaptureBackendTrait {
         Ok(compatible_formats)
     }
 
+    /// A hashmap of [`Resolution`]s mapped to the highest framerate available at that resolution. Not sorted!
+    /// # Errors
+    /// This will error if the camera is not queryable or a query operation has failed. Some backends will error this out as a Unsupported Operation ([`UnsupportedOperationError`](crate::error::NokhwaError::UnsupportedOperationError)).
+    fn max_fps_table(
+        &mut self,
+        fourcc: FrameFormat,
+    ) -> Result<HashMap<Resolution, u32>, NokhwaError> {
+        Ok(self
+            .compatible_list_by_resolution(fourcc)?
+            .into_iter()
+            .filter_map(|(resolution, fps_list)| {
+                fps_list
+                    .into_iter()
+                    .max()
+                    .map(|max_fps| (resolution, max_fps))
+            })
+            .collect())
+    }
+
+    /// Gets the true highest frame rate of `format` at `resolution`, i.e. the inverse of the shortest frame interval the driver reports.
+    /// Unlike [`max_fps_table()`](CaptureBackendTrait::max_fps_table), this also covers cameras with continuous (stepwise) frame intervals.
+    ///
+    /// By default, this returns `None`.
+    fn true_max_fps(&self, _format: FrameFormat, _resolution: Resolution) -> Option<u32> {
+        None
+    }
+
     /// A Vector of compatible [`FrameFormat`]s. Will only return 2 elements at most.
     /// # Errors
     /// This will error if the camera is not queryable or a query operation has failed. Some backends will error this out as a Unsupported Operation ([`UnsupportedOperationError`](crate::error::NokhwaError::UnsupportedOperationError)).
     fn compatible_fourcc(&mut self) -> Result<Vec<FrameFormat>, NokhwaError>;
 
+    /// A Vector of the pixel formats reported by the driver, with their descriptions and flags. See [`FormatInfo`].
+    ///
+    /// By default, this returns an empty list.
+    fn detailed_formats(&self) -> Vec<FormatInfo> {
+        vec![]
+    }
+
     /// Gets the current camera resolution (See: [`Resolution`], [`CameraFormat`]). This will force refresh to the current latest if it has changed.
     fn resolution(&self) -> Resolution;
 
@@ -114,6 +164,13 @@ pub trait CaptureBackendTrait {
     /// Gets the current camera's frame format (See: [`FrameFormat`], [`CameraFormat`]). This will force refresh to the current latest if it has changed.
     fn frame_format(&self) -> FrameFormat;
 
+    /// Gets the exact four character code of the pixel format the driver is using, e.g. `*b"MJPG"`. This can also describe formats that have no [`FrameFormat`].
+    ///
+    /// By default, this returns `None`.
+    fn current_fourcc(&self) -> Option<[u8; 4]> {
+        None
+    }
+
     /// Will set the current [`FrameFormat`]
     /// This will reset the current stream if used while stream is opened.
     ///
@@ -133,6 +190,48 @@ pub trait CaptureBackendTrait {
     /// If the list cannot be collected, this will error. This can be treated as a "nothing supported".
     fn camera_controls(&self) -> Result<Vec<CameraControl>, NokhwaError>;
 
+    /// Same as [`camera_controls()`](CaptureBackendTrait::camera_controls), but in a deterministic order, so e.g. a settings panel does not reshuffle between runs or cameras.
+    ///
+    /// By default, this sorts by [`KnownCameraControl`]. Backends with control classes (e.g. V4L2) sort by class first, then by ID.
+    /// # Errors
+    /// If the list cannot be collected, this will error.
+    fn camera_controls_sorted(&self) -> Result<Vec<CameraControl>, NokhwaError> {
+        let mut controls = self.camera_controls()?;
+        controls.sort_by_key(CameraControl::control);
+        Ok(controls)
+    }
+
+    /// Same as [`camera_controls()`](CaptureBackendTrait::camera_controls), but also returns the controls that failed to be read, with the reason why.
+    ///
+    /// Each control is read independently, so one unreadable control does not prevent the others from being returned.
+    /// By default, this calls [`camera_controls()`](CaptureBackendTrait::camera_controls) and reports no failures.
+    /// # Errors
+    /// If the list of controls could not be queried at all, this will error.
+    fn camera_controls_partial(&self) -> Result<PartialCameraControls, NokhwaError> {
+        Ok((self.camera_controls()?, vec![]))
+    }
+
+    /// Same as [`camera_controls()`](CaptureBackendTrait::camera_controls), but only the controls that can currently be changed, e.g. for a settings panel.
+    ///
+    /// By default, this leaves out controls flagged [`ReadOnly`](KnownCameraControlFlag::ReadOnly) or [`Disabled`](KnownCameraControlFlag::Disabled).
+    /// Backends may also leave out controls that are locked for other reasons (e.g. grabbed by a running stream on V4L2).
+    /// # Errors
+    /// If the list cannot be collected, this will error.
+    fn writable_controls(&self) -> Result<Vec<CameraControl>, NokhwaError> {
+        Ok(self
+            .camera_controls()?
+            .into_iter()
+            .filter(|control| {
+                !control.flag().iter().any(|flag| {
+                    matches!(
+                        flag,
+                        KnownCameraControlFlag::ReadOnly | KnownCameraControlFlag::Disabled
+                    )
+                })
+            })
+            .collect())
+    }
+
     /// Sets the control to `control` in the camera.
     /// Usually, the pipeline is calling [`camera_control()`](CaptureBackendTrait::camera_control), getting a camera control that way
     /// then calling [`value()`](CameraControl::value()) to get a [`ControlValueSetter`] and setting the value that way.
@@ -145,6 +244,192 @@ pub trait CaptureBackendTrait {
         value: ControlValueSetter,
     ) -> Result<(), NokhwaError>;
 
+    /// Sets several controls in one call, e.g. when applying a saved profile. Unlike [`set_camera_control()`](CaptureBackendTrait::set_camera_control), the values are not read back to verify them.
+    ///
+    /// A control being rejected does not stop the others. The result of each is returned, in the order of `controls`.
+    /// By default, this sets them one by one. Backends may set them atomically where the driver supports it (e.g. `VIDIOC_S_EXT_CTRLS` on V4L2, per control class).
+    /// # Errors
+    /// If the controls cannot be queried, this will error.
+    fn set_camera_controls(
+        &mut self,
+        controls: &[(KnownCameraControl, ControlValueSetter)],
+    ) -> Result<CameraControlResults, NokhwaError> {
+        Ok(controls
+            .iter()
+            .map(|(id, value)| (*id, self.set_camera_control(*id, value.clone())))
+            .collect())
+    }
+
+    /// Sets the control back to its default value.
+    ///
+    /// By default, this reads the default from the control's [`ControlValueDescription`](crate::types::ControlValueDescription) and sets it with [`set_camera_control()`](CaptureBackendTrait::set_camera_control).
+    /// # Errors
+    /// If the `control` is not supported, has no known default, or there was an error setting the control, this will error.
+    fn reset_camera_control(&mut self, id: KnownCameraControl) -> Result<(), NokhwaError> {
+        let control = self.camera_control(id)?;
+        let default =
+            control
+                .description()
+                .default_value()
+                .ok_or_else(|| NokhwaError::SetPropertyError {
+                    property: id.to_string(),
+                    value: control.value().to_string(),
+                    error: "No default value".to_string(),
+                })?;
+        self.set_camera_control(id, default)
+    }
+
+    /// Resets every [writable](CaptureBackendTrait::writable_controls) control to its default value, e.g. for a "restore defaults" button.
+    ///
+    /// A control failing to reset does not stop the others. Those are returned with the reason why, e.g. a manual control that is inactive because its automatic mode was restored first.
+    /// # Errors
+    /// If the list of controls cannot be collected, this will error.
+    fn reset_all_controls(
+        &mut self,
+    ) -> Result<Vec<(KnownCameraControl, NokhwaError)>, NokhwaError> {
+        let failed = self
+            .writable_controls()?
+            .into_iter()
+            .filter_map(|control| {
+                self.reset_camera_control(control.control())
+                    .err()
+                    .map(|why| (control.control(), why))
+            })
+            .collect();
+        Ok(failed)
+    }
+
+    /// Lets the driver vary the frame duration inside `range`, so the frame rate can drop to [`FrameRateRange::min()`] (e.g. when auto exposure needs more time in low light) instead of staying fixed.
+    ///
+    /// The current [`CameraFormat`]'s frame rate acts as the upper bound. This is also applied when opening a camera with [`RequestedFormatType::FrameRateRange`](crate::types::RequestedFormatType::FrameRateRange).
+    /// # Errors
+    /// By default, this returns [`NotImplementedError`](crate::error::NokhwaError::NotImplementedError).
+    fn set_frame_rate_range(&mut self, range: FrameRateRange) -> Result<(), NokhwaError> {
+        Err(NokhwaError::NotImplementedError(format!(
+            "Frame rate range {range} is not supported by this backend"
+        )))
+    }
+
+    /// Gets the groups of controls that must change together (e.g. Auto White Balance and White Balance Temperature). See [`ControlDependency`].
+    ///
+    /// By default, this returns an empty list (no known dependencies).
+    fn control_dependencies(&self) -> Vec<ControlDependency> {
+        vec![]
+    }
+
+    /// Gets the supported controls as a tree: auto-mode controls (e.g. Auto Exposure) list the manual controls that depend on them (e.g. Exposure, Iris) as children.
+    ///
+    /// This is built from [`camera_controls()`](CaptureBackendTrait::camera_controls) and [`control_dependencies()`](CaptureBackendTrait::control_dependencies).
+    /// Controls that are not part of a supported dependency group are returned as roots without children. Controls that cannot be read are omitted.
+    fn control_tree(&self) -> Vec<ControlNode> {
+        let controls = self.camera_controls().unwrap_or_default();
+        let dependencies = self
+            .control_dependencies()
+            .into_iter()
+            .filter(|dependency| {
+                controls
+                    .iter()
+                    .any(|control| control.control() == dependency.parent())
+            })
+            .collect::<Vec<ControlDependency>>();
+
+        controls
+            .iter()
+            .filter(|control| {
+                !dependencies
+                    .iter()
+                    .any(|dependency| dependency.dependents().contains(&control.control()))
+            })
+            .map(|parent| {
+                let children = dependencies
+                    .iter()
+                    .filter(|dependency| dependency.parent() == parent.control())
+                    .flat_map(|dependency| dependency.dependents().iter())
+                    .filter_map(|dependent| {
+                        controls
+                            .iter()
+                            .find(|control| control.control() == *dependent)
+                    })
+                    .map(|child| ControlNode::new(child.clone(), vec![]))
+                    .collect();
+                ControlNode::new(parent.clone(), children)
+            })
+            .collect()
+    }
+
+    /// Gets the sensor temperature in degrees Celsius, if the camera exposes it (usually as a read-only control on industrial cameras).
+    ///
+    /// By default, this returns `None`.
+    fn sensor_temperature(&self) -> Option<f32> {
+        None
+    }
+
+    /// Turns the camera's LED (privacy/in-use indicator) on or off, if the camera lets it be controlled.
+    /// # Errors
+    /// If the camera has no controllable LED, this will error with [`UnsupportedOperationError`](crate::error::NokhwaError::UnsupportedOperationError).
+    /// By default, this always errors.
+    fn set_led(&mut self, on: bool) -> Result<(), NokhwaError> {
+        let _ = on;
+        Err(NokhwaError::UnsupportedOperationError(self.backend()))
+    }
+
+    /// Gets the speed of the USB connection the camera is plugged in with. This can explain why high resolution/frame rate modes are unavailable (e.g. on a USB 2.0 port).
+    ///
+    /// By default, this returns `None`.
+    fn usb_speed(&self) -> Option<UsbSpeed> {
+        None
+    }
+
+    /// Resets the camera as a last resort, when it hangs in a way that no control or format change recovers it from, then opens it again with the current [`CameraFormat`].
+    /// If the stream was open, it is opened again too.
+    ///
+    /// The device may come back under another [`CameraIndex`](crate::types::CameraIndex). The new one is in [`camera_info()`](CaptureBackendTrait::camera_info).
+    /// # Errors
+    /// If the backend does not support this (by default), the reset is not permitted, or the camera does not come back, this will error.
+    fn hard_reset(&mut self) -> Result<(), NokhwaError> {
+        Err(NokhwaError::UnsupportedOperationError(self.backend()))
+    }
+
+    /// Gets the [`Colorimetry`] of the current format (primaries, transfer function, matrix and range), as reported by the driver after format negotiation.
+    ///
+    /// By default, this returns `None`.
+    fn colorimetry(&self) -> Option<Colorimetry> {
+        None
+    }
+
+    /// Gets the readout line time and direction of a rolling shutter sensor, if the driver exposes them.
+    ///
+    /// By default, this returns `None`.
+    fn rolling_shutter_info(&self) -> Option<RollingShutter> {
+        None
+    }
+
+    /// Gets how the driver adjusted the format fulfilled from the [`RequestedFormat`](crate::types::RequestedFormat) this camera was opened with (e.g. 60 FPS requested, 30 FPS negotiated).
+    ///
+    /// Adjustments are only accepted if the request was made with [`with_lenient(true)`](crate::types::RequestedFormat::with_lenient). By default, this returns `None`.
+    fn negotiated_vs_requested(&self) -> Option<FormatNegotiation> {
+        None
+    }
+
+    /// Hints the size in bytes of each frame buffer, e.g. to keep the allocations for variable-size formats like MJPEG small on memory constrained devices.
+    /// `None` lets the driver pick the size again. Drivers are free to ignore or round the hint, check [`buffer_size()`](CaptureBackendTrait::buffer_size) for the result.
+    ///
+    /// This will reset the current stream if used while stream is opened.
+    /// # Errors
+    /// By default, this returns [`NotImplementedError`](crate::error::NokhwaError::NotImplementedError).
+    fn set_buffer_size_hint(&mut self, size: Option<u32>) -> Result<(), NokhwaError> {
+        Err(NokhwaError::NotImplementedError(format!(
+            "Buffer size hint {size:?} is not supported by this backend"
+        )))
+    }
+
+    /// Gets the size in bytes of each frame buffer the driver allocates for the current format.
+    ///
+    /// By default, this returns `None`.
+    fn buffer_size(&self) -> Option<u32> {
+        None
+    }
+
     /// Will open the camera stream with set parameters. This will be called internally if you try and call [`frame()`](CaptureBackendTrait::frame()) before you call [`open_stream()`](CaptureBackendTrait::open_stream()).
     /// # Errors
     /// If the specific backend fails to open the camera (e.g. already taken, busy, doesn't exist anymore) this will error.
@@ -155,6 +440,8 @@ pub trait CaptureBackendTrait {
 
     /// Will get a frame from the camera as a [`Buffer`]. Depending on the backend, if you have not called [`open_stream()`](CaptureBackendTrait::open_stream()) before you called this,
     /// it will either return an error.
+    ///
+    /// The returned [`Buffer`] owns a copy of the frame data, so it is safe to keep across captures.
     /// # Errors
     /// If the backend fails to get the frame (e.g. already taken, busy, doesn't exist anymore), the decoding fails (e.g. MJPEG -> u8), or [`open_stream()`](CaptureBackendTrait::open_stream()) has not been called yet,
     /// this will error.
@@ -165,15 +452,71 @@ pub trait CaptureBackendTrait {
     /// If the backend fails to get the frame (e.g. already taken, busy, doesn't exist anymore), or [`open_stream()`](CaptureBackendTrait::open_stream()) has not been called yet, this will error.
     fn frame_raw(&mut self) -> Result<Cow<[u8]>, NokhwaError>;
 
+    /// Same as [`frame_raw()`](CaptureBackendTrait::frame_raw()), but gives up if no frame arrives within `timeout` instead of blocking indefinitely (e.g. when the camera was unplugged mid-stream).
+    ///
+    /// By default, this returns a `NotImplementedError`.
+    /// # Errors
+    /// If no frame arrives in time, this returns a [`NokhwaError::TimeoutError`]. Otherwise, this errors like [`frame_raw()`](CaptureBackendTrait::frame_raw()).
+    fn frame_raw_timeout(&mut self, _timeout: Duration) -> Result<Cow<[u8]>, NokhwaError> {
+        Err(NokhwaError::NotImplementedError(
+            "Frame capture with a timeout".to_string(),
+        ))
+    }
+
+    /// Same as [`frame()`](CaptureBackendTrait::frame()), but gives up if no frame arrives within `timeout`, like [`frame_raw_timeout()`](CaptureBackendTrait::frame_raw_timeout()).
+    ///
+    /// By default, this returns a `NotImplementedError`.
+    /// # Errors
+    /// If no frame arrives in time, this returns a [`NokhwaError::TimeoutError`]. Otherwise, this errors like [`frame()`](CaptureBackendTrait::frame()).
+    fn frame_timeout(&mut self, _timeout: Duration) -> Result<Buffer, NokhwaError> {
+        Err(NokhwaError::NotImplementedError(
+            "Frame capture with a timeout".to_string(),
+        ))
+    }
+
+    /// Gets the capture timestamp of the last frame from [`frame()`](CaptureBackendTrait::frame()) or [`frame_raw()`](CaptureBackendTrait::frame_raw()), as reported by the driver.
+    /// The clock it is measured against is backend specific (for V4L2, usually `CLOCK_MONOTONIC`), so only differences between timestamps are meaningful.
+    ///
+    /// By default, this returns `None`.
+    fn frame_timestamp(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Gets the number of bytes the driver filled in the last frame from [`frame()`](CaptureBackendTrait::frame()) or [`frame_raw()`](CaptureBackendTrait::frame_raw()).
+    /// For compressed formats (e.g. MJPEG) this is the size of the compressed frame, which can be smaller than the buffer returned, e.g. to track the bitrate.
+    ///
+    /// By default, this returns `None`.
+    fn last_frame_bytes(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns true if the last frame from [`frame()`](CaptureBackendTrait::frame()) or [`frame_raw()`](CaptureBackendTrait::frame_raw()) can be decoded on its own (a keyframe, or I-frame).
+    /// Consumers of inter-frame compressed streams can wait for this before they start decoding or recording.
+    /// Formats without inter-frame compression (e.g. MJPEG, YUYV) consist only of keyframes.
+    ///
+    /// By default, this returns `None`.
+    fn frame_is_keyframe(&self) -> Option<bool> {
+        None
+    }
+
     /// The minimum buffer size needed to write the current frame. If `alpha` is true, it will instead return the minimum size of the buffer with an alpha channel as well.
-    /// This assumes that you are decoding to RGB/RGBA for [`FrameFormat::MJPEG`] or [`FrameFormat::YUYV`] and Luma8/LumaA8 for [`FrameFormat::GRAY`]
+    /// This assumes that you are decoding to RGB/RGBA for [`FrameFormat::MJPEG`], YUV, and raw RGB/BGR formats and Luma8/LumaA8 for [`FrameFormat::GRAY`], [`FrameFormat::GRAY16LE`], and [`FrameFormat::GRAY16BE`]
     #[must_use]
     fn decoded_buffer_size(&self, alpha: bool) -> usize {
         let cfmt = self.camera_format();
         let resolution = cfmt.resolution();
         let pxwidth = match cfmt.format() {
-            FrameFormat::MJPEG | FrameFormat::YUYV | FrameFormat::RAWRGB | FrameFormat::NV12 => 3,
-            FrameFormat::GRAY => 1,
+            FrameFormat::MJPEG
+            | FrameFormat::YUYV
+            | FrameFormat::UYVY
+            | FrameFormat::NV12
+            | FrameFormat::I420
+            | FrameFormat::RAWRGB
+            | FrameFormat::RAWBGR
+            | FrameFormat::H264
+            | FrameFormat::H265
+            | FrameFormat::Custom(_) => 3,
+            FrameFormat::GRAY | FrameFormat::GRAY16LE | FrameFormat::GRAY16BE => 1,
         };
         if alpha {
             return (resolution.width() * resolution.height() * (pxwidth + 1)) as usize;
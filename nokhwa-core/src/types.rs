@@ -3,9 +3,10 @@ use crate::{error::NokhwaError, pixel_format::FormatDecoder};
 use serde::{Deserialize, Serialize};
 use std::{
     borrow::Borrow,
-    cmp::Ordering,
+    cmp::{Ordering, Reverse},
     fmt::{Display, Formatter},
     str::FromStr,
+    time::Duration,
 };
 
 /// Tells the init function what camera format to pick.
@@ -15,6 +16,11 @@ use std::{
 /// - `HighestFrameRate(Option<Resolution>)`: Pick the highest frame rate for the given [`Resolution`] (the `Option<Resolution>`). If it is `None`, it will pick the highest possinle framerate.
 /// - `Exact`: Pick the exact [`CameraFormat`] provided.
 /// - `Closest`: Pick the closest [`CameraFormat`] provided in order of [`FrameFormat`], [`Resolution`], and FPS. Note that if the [`FrameFormat`] does not exist, this will fail to resolve.
+/// - `FrameRateRange`: Pick the frame rate inside the [`FrameRateRange`] closest to its target, then the highest [`Resolution`]. Backends that support it will let the driver lower the frame rate down to the range's minimum (e.g. when auto exposure needs longer frames in the dark).
+/// - `Preferred`: Pick the [`FrameFormat`] the driver lists first (usually the one the manufacturer intends as the default), then the highest [`Resolution`] and frame rate of it.
+/// - `Nearest`: Pick the [`CameraFormat`] with the lowest [`format_distance()`] to the one provided, weighing a [`Resolution`] mismatch more heavily than a frame rate mismatch. Unlike `Closest`, this resolves as long as any format is available, preferring the requested [`FrameFormat`] on ties.
+/// - `ClosestAspectRatio`: Pick the [`Resolution`] whose aspect ratio (width:height) is nearest to `ratio`, e.g. `(16, 9)`, optionally only of the given [`FrameFormat`]. Ties go to the highest [`Resolution`], then frame rate.
+/// - `LowestLatency`: Pick an uncompressed [`FrameFormat`] if there is one (no decode delay), then the highest frame rate, then the [`Resolution`] closest to 1280x720 without exceeding it. Backends that support it also stream with only 2 buffers, so frames are not queued up.
 /// - `None`: Pick a random [`CameraFormat`]
 #[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
@@ -25,6 +31,14 @@ pub enum RequestedFormatType {
     HighestFrameRate(u32),
     Exact(CameraFormat),
     Closest(CameraFormat),
+    FrameRateRange(FrameRateRange),
+    Preferred,
+    Nearest(CameraFormat),
+    ClosestAspectRatio {
+        ratio: (u32, u32),
+        format: Option<FrameFormat>,
+    },
+    LowestLatency,
     None,
 }
 
@@ -40,11 +54,16 @@ impl Display for RequestedFormatType {
     }
 }
 
+// The largest resolution `RequestedFormatType::LowestLatency` prefers, 1280x720.
+const LOW_LATENCY_MAX_PIXELS: u64 = 1280 * 720;
+
 /// A request to the camera for a valid [`CameraFormat`]
 #[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[allow(clippy::struct_field_names)]
 pub struct RequestedFormat<'a> {
     requested_format: RequestedFormatType,
     wanted_decoder: &'a [FrameFormat],
+    lenient: bool,
 }
 
 impl RequestedFormat<'_> {
@@ -55,6 +74,7 @@ impl RequestedFormat<'_> {
         RequestedFormat {
             requested_format: requested,
             wanted_decoder: Decoder::FORMATS,
+            lenient: false,
         }
     }
 
@@ -68,15 +88,41 @@ impl RequestedFormat<'_> {
         RequestedFormat {
             requested_format: requested,
             wanted_decoder: decoder,
+            lenient: false,
         }
     }
 
+    /// Sets whether the camera should still open if the driver adjusts the fulfilled [`CameraFormat`] (e.g. capping 60 FPS to 30 FPS over USB 2).
+    ///
+    /// By default this is `false`, and such an adjustment is an error. When `true`, the adjusted format is used and the difference
+    /// can be inspected with `negotiated_vs_requested()`. See [`FormatNegotiation`].
+    #[must_use]
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Returns true if driver adjustments to the fulfilled format are accepted. See [`with_lenient()`](Self::with_lenient).
+    #[must_use]
+    pub fn lenient(&self) -> bool {
+        self.lenient
+    }
+
     /// Gets the [`RequestedFormatType`]
     #[must_use]
     pub fn requested_format_type(&self) -> RequestedFormatType {
         self.requested_format
     }
 
+    /// Gets the [`FrameRateRange`], if this request is a [`RequestedFormatType::FrameRateRange`]
+    #[must_use]
+    pub fn frame_rate_range(&self) -> Option<FrameRateRange> {
+        match self.requested_format {
+            RequestedFormatType::FrameRateRange(range) => Some(range),
+            _ => None,
+        }
+    }
+
     /// Fulfill the requested using a list of all available formats.
     ///
     /// See [`RequestedFormatType`] for more details.
@@ -118,20 +164,20 @@ impl RequestedFormat<'_> {
                     .filter(|x| x.resolution == res)
                     .copied()
                     .collect::<Vec<CameraFormat>>();
-                formats.sort_by(|a, b| a.frame_rate.cmp(&b.frame_rate));
+                formats.sort_by(|a, b| a.frame_rate().cmp(&b.frame_rate()));
                 let highest_fps = match formats.last() {
-                    Some(cf) => cf.frame_rate,
+                    Some(cf) => cf.frame_rate(),
                     None => return None,
                 };
                 formats
                     .into_iter()
-                    .filter(|x| x.frame_rate == highest_fps)
+                    .filter(|x| x.frame_rate() == highest_fps)
                     .last()
             }
             RequestedFormatType::HighestFrameRate(fps) => {
                 let mut formats = all_formats
                     .iter()
-                    .filter(|x| x.frame_rate == fps)
+                    .filter(|x| x.frame_rate() == fps)
                     .copied()
                     .collect::<Vec<CameraFormat>>();
                 formats.sort_by(|a, b| a.resolution.cmp(&b.resolution));
@@ -195,6 +241,84 @@ impl RequestedFormat<'_> {
                 let frame_rate = framerate_map.first()?.1;
                 Some(CameraFormat::new(resolution, c.format(), frame_rate))
             }
+            RequestedFormatType::FrameRateRange(range) => all_formats
+                .iter()
+                .filter(|fmt| {
+                    range.contains(fmt.frame_rate()) && self.wanted_decoder.contains(&fmt.format())
+                })
+                .min_by_key(|fmt| {
+                    (
+                        fmt.frame_rate().abs_diff(range.target()),
+                        Reverse(fmt.resolution()),
+                    )
+                })
+                .copied(),
+            RequestedFormatType::Preferred => {
+                // backends list formats in the order the driver reports them
+                let preferred = all_formats
+                    .iter()
+                    .find(|fmt| self.wanted_decoder.contains(&fmt.format()))?
+                    .format();
+                all_formats
+                    .iter()
+                    .filter(|fmt| fmt.format() == preferred)
+                    .max_by_key(|fmt| (fmt.resolution(), fmt.frame_rate()))
+                    .copied()
+            }
+            RequestedFormatType::Nearest(requested) => all_formats
+                .iter()
+                .filter(|fmt| self.wanted_decoder.contains(&fmt.format()))
+                .min_by(|a, b| {
+                    let distance = |fmt: &CameraFormat| {
+                        format_distance(
+                            fmt,
+                            &requested,
+                            NEAREST_RESOLUTION_WEIGHT,
+                            NEAREST_FRAME_RATE_WEIGHT,
+                        )
+                    };
+                    distance(a).total_cmp(&distance(b)).then_with(|| {
+                        (b.format() == requested.format()).cmp(&(a.format() == requested.format()))
+                    })
+                })
+                .copied(),
+            RequestedFormatType::ClosestAspectRatio {
+                ratio: (ratio_width, ratio_height),
+                format,
+            } => {
+                // `|width / height - ratio_width / ratio_height|` times `height * ratio_height`, exact in integers
+                let scaled_distance = |fmt: &CameraFormat| {
+                    (u64::from(fmt.width()) * u64::from(ratio_height))
+                        .abs_diff(u64::from(fmt.height()) * u64::from(ratio_width))
+                };
+                all_formats
+                    .iter()
+                    .filter(|fmt| {
+                        self.wanted_decoder.contains(&fmt.format())
+                            && (format.is_none() || format == Some(fmt.format()))
+                    })
+                    .min_by(|a, b| {
+                        // cross multiply by the other's height to compare the distances without dividing
+                        (u128::from(scaled_distance(a)) * u128::from(b.height()))
+                            .cmp(&(u128::from(scaled_distance(b)) * u128::from(a.height())))
+                            .then_with(|| b.resolution().cmp(&a.resolution()))
+                            .then_with(|| b.frame_rate().cmp(&a.frame_rate()))
+                    })
+                    .copied()
+            }
+            RequestedFormatType::LowestLatency => all_formats
+                .iter()
+                .filter(|fmt| self.wanted_decoder.contains(&fmt.format()))
+                .min_by_key(|fmt| {
+                    let pixels = u64::from(fmt.width()) * u64::from(fmt.height());
+                    (
+                        fmt.format().decode_cost() == DecodeCost::Expensive,
+                        pixels > LOW_LATENCY_MAX_PIXELS,
+                        Reverse(fmt.frame_rate()),
+                        pixels.abs_diff(LOW_LATENCY_MAX_PIXELS),
+                    )
+                })
+                .copied(),
             RequestedFormatType::None => all_formats
                 .iter()
                 .find(|fmt| self.wanted_decoder.contains(&fmt.format()))
@@ -203,6 +327,154 @@ impl RequestedFormat<'_> {
     }
 }
 
+/// The default weight of the [`Resolution`] mismatch in [`format_distance()`], used by [`RequestedFormatType::Nearest`].
+/// With the frame rate weighing `1.0`, a resolution off by a factor of 2 in both dimensions costs as much as a frame rate off by a factor of 16.
+pub const NEAREST_RESOLUTION_WEIGHT: f64 = 2.0;
+/// The default weight of the frame rate mismatch in [`format_distance()`], used by [`RequestedFormatType::Nearest`].
+pub const NEAREST_FRAME_RATE_WEIGHT: f64 = 1.0;
+
+/// Scores how far `candidate` is from `requested`, lower being closer. `0.0` means the [`Resolution`] and frame rate match exactly, the [`FrameFormat`] is not considered.
+///
+/// Mismatches are measured as ratios, so 640x480 is as far from 1280x960 as 1280x960 is from 2560x1920: the resolution term is
+/// `|ln(width / requested width)| + |ln(height / requested height)|`, and the frame rate term is `|ln(fps / requested fps)|`.
+/// Each term is multiplied by its weight, see [`NEAREST_RESOLUTION_WEIGHT`] and [`NEAREST_FRAME_RATE_WEIGHT`] for the ones [`RequestedFormatType::Nearest`] uses.
+#[must_use]
+pub fn format_distance(
+    candidate: &CameraFormat,
+    requested: &CameraFormat,
+    resolution_weight: f64,
+    frame_rate_weight: f64,
+) -> f64 {
+    let log_ratio = |value: u32, target: u32| {
+        (f64::from(value.max(1)) / f64::from(target.max(1)))
+            .ln()
+            .abs()
+    };
+    let resolution_distance = log_ratio(candidate.width(), requested.width())
+        + log_ratio(candidate.height(), requested.height());
+    let frame_rate_distance = log_ratio(candidate.frame_rate(), requested.frame_rate());
+    resolution_distance * resolution_weight + frame_rate_distance * frame_rate_weight
+}
+
+/// A range of acceptable frame rates with a preferred target, used by [`RequestedFormatType::FrameRateRange`].
+///
+/// The driver may vary the frame duration between `min` and the negotiated frame rate,
+/// so that low light scenes slow down instead of underexposing.
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct FrameRateRange {
+    min: u32,
+    target: u32,
+    max: u32,
+}
+
+impl FrameRateRange {
+    /// Creates a new [`FrameRateRange`]. `min` and `max` are swapped if given out of order, and `target` is clamped into the range.
+    #[must_use]
+    pub fn new(min: u32, target: u32, max: u32) -> Self {
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+        FrameRateRange {
+            min,
+            target: target.clamp(min, max),
+            max,
+        }
+    }
+
+    /// Get the minimum acceptable frame rate.
+    #[must_use]
+    pub fn min(&self) -> u32 {
+        self.min
+    }
+
+    /// Get the preferred frame rate.
+    #[must_use]
+    pub fn target(&self) -> u32 {
+        self.target
+    }
+
+    /// Get the maximum acceptable frame rate.
+    #[must_use]
+    pub fn max(&self) -> u32 {
+        self.max
+    }
+
+    /// Returns true if `frame_rate` is inside this range (inclusive).
+    #[must_use]
+    pub fn contains(&self, frame_rate: u32) -> bool {
+        (self.min..=self.max).contains(&frame_rate)
+    }
+}
+
+impl Display for FrameRateRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{} FPS (target {})", self.min, self.max, self.target)
+    }
+}
+
+/// The [`CameraFormat`] fulfilled from a [`RequestedFormat`] next to the one the driver actually negotiated.
+///
+/// Drivers may silently adjust a format, e.g. capping the frame rate when there is not enough USB bandwidth.
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct FormatNegotiation {
+    requested: CameraFormat,
+    negotiated: CameraFormat,
+}
+
+impl FormatNegotiation {
+    /// Creates a new [`FormatNegotiation`]
+    #[must_use]
+    pub fn new(requested: CameraFormat, negotiated: CameraFormat) -> Self {
+        FormatNegotiation {
+            requested,
+            negotiated,
+        }
+    }
+
+    /// Gets the [`CameraFormat`] that was asked of the driver.
+    #[must_use]
+    pub fn requested(&self) -> CameraFormat {
+        self.requested
+    }
+
+    /// Gets the [`CameraFormat`] the driver is actually using.
+    #[must_use]
+    pub fn negotiated(&self) -> CameraFormat {
+        self.negotiated
+    }
+
+    /// Returns true if the driver used the requested format as-is.
+    #[must_use]
+    pub fn is_exact(&self) -> bool {
+        self.requested == self.negotiated
+    }
+
+    /// Returns true if the driver changed the [`Resolution`].
+    #[must_use]
+    pub fn resolution_changed(&self) -> bool {
+        self.requested.resolution() != self.negotiated.resolution()
+    }
+
+    /// Returns true if the driver changed the [`FrameFormat`].
+    #[must_use]
+    pub fn format_changed(&self) -> bool {
+        self.requested.format() != self.negotiated.format()
+    }
+
+    /// Returns true if the driver changed the frame rate.
+    #[must_use]
+    pub fn frame_rate_changed(&self) -> bool {
+        self.requested.frame_rate() != self.negotiated.frame_rate()
+    }
+}
+
+/// Formats as e.g. `requested 1280x720@60/MJPEG, got 1280x720@30/MJPEG`.
+impl Display for FormatNegotiation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "requested {}, got {}", self.requested, self.negotiated)
+    }
+}
+
 impl Display for RequestedFormat<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{self:?}")
@@ -287,10 +559,16 @@ impl TryFrom<CameraIndex> for usize {
 
 /// Describes a frame format (i.e. how the bytes themselves are encoded). Often called `FourCC`.
 /// - YUYV is a mathematical color space. You can read more [here.](https://en.wikipedia.org/wiki/YCbCr)
+/// - UYVY is the same as YUYV, with the luma and chroma bytes swapped (U, Y, V, Y).
 /// - NV12 is same as above. Note that a partial compression (e.g. [16, 235] may be coerced to [0, 255].
+/// - I420 (also known as YU12) is the same as NV12, with the U and V samples in two separate planes instead of one interleaved plane.
 /// - MJPEG is a motion-jpeg compressed frame, it allows for high frame rates.
 /// - GRAY is a grayscale image format, usually for specialized cameras such as IR Cameras.
+/// - GRAY16LE/GRAY16BE are 16-bit grayscale formats (e.g. depth or thermal cameras), in little/big endian byte order. See [`FrameFormat::endianness()`].
 /// - RAWRGB is a Raw RGB888 format.
+/// - RAWBGR is a Raw BGR888 format, RGB888 with the red and blue bytes swapped.
+/// - H264/H265 are H.264 (AVC) and H.265 (HEVC) compressed video, as emitted by some UVC cameras. `nokhwa` does not decode them, frames are passed through as-is (e.g. to a muxer).
+/// - Custom is any other format the driver reports, kept as its raw `FourCC` (e.g. `Z16 ` for depth or `pRAA` for Bayer). Frames are passed through undecoded, so request it explicitly with [`RequestedFormat::with_formats()`].
 #[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum FrameFormat {
@@ -298,7 +576,180 @@ pub enum FrameFormat {
     YUYV,
     NV12,
     GRAY,
+    GRAY16LE,
+    GRAY16BE,
     RAWRGB,
+    UYVY,
+    I420,
+    RAWBGR,
+    H264,
+    H265,
+    Custom([u8; 4]),
+}
+
+impl FrameFormat {
+    /// Gets the byte order of each sample for multi-byte formats ([`FrameFormat::GRAY16LE`], [`FrameFormat::GRAY16BE`]).
+    /// Returns `None` for formats made of single bytes, where byte order does not matter.
+    #[must_use]
+    pub fn endianness(&self) -> Option<Endianness> {
+        match self {
+            FrameFormat::GRAY16LE => Some(Endianness::Little),
+            FrameFormat::GRAY16BE => Some(Endianness::Big),
+            FrameFormat::MJPEG
+            | FrameFormat::YUYV
+            | FrameFormat::NV12
+            | FrameFormat::GRAY
+            | FrameFormat::RAWRGB
+            | FrameFormat::UYVY
+            | FrameFormat::I420
+            | FrameFormat::RAWBGR
+            | FrameFormat::H264
+            | FrameFormat::H265
+            | FrameFormat::Custom(_) => None,
+        }
+    }
+
+    /// Gets the MIME type of this format, e.g. `video/x-motion-jpeg` or `video/x-raw;format=YUY2`, for tagging frames in streaming pipelines.
+    /// Raw formats carry the [`codec_name()`](FrameFormat::codec_name) as their `format` parameter. [`FrameFormat::Custom`] is plain `video/x-raw`.
+    #[must_use]
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            FrameFormat::MJPEG => "video/x-motion-jpeg",
+            FrameFormat::YUYV => "video/x-raw;format=YUY2",
+            FrameFormat::NV12 => "video/x-raw;format=NV12",
+            FrameFormat::GRAY => "video/x-raw;format=GRAY8",
+            FrameFormat::GRAY16LE => "video/x-raw;format=GRAY16_LE",
+            FrameFormat::GRAY16BE => "video/x-raw;format=GRAY16_BE",
+            FrameFormat::RAWRGB => "video/x-raw;format=RGB",
+            FrameFormat::UYVY => "video/x-raw;format=UYVY",
+            FrameFormat::I420 => "video/x-raw;format=I420",
+            FrameFormat::RAWBGR => "video/x-raw;format=BGR",
+            FrameFormat::H264 => "video/x-h264",
+            FrameFormat::H265 => "video/x-h265",
+            FrameFormat::Custom(_) => "video/x-raw",
+        }
+    }
+
+    /// Gets the GStreamer-style name of this format, e.g. `MJPG`, `YUY2`, or `GRAY16_LE`. [`FrameFormat::Custom`] is `UNKNOWN`.
+    #[must_use]
+    pub fn codec_name(&self) -> &'static str {
+        match self {
+            FrameFormat::MJPEG => "MJPG",
+            FrameFormat::YUYV => "YUY2",
+            FrameFormat::NV12 => "NV12",
+            FrameFormat::GRAY => "GRAY8",
+            FrameFormat::GRAY16LE => "GRAY16_LE",
+            FrameFormat::GRAY16BE => "GRAY16_BE",
+            FrameFormat::RAWRGB => "RGB",
+            FrameFormat::UYVY => "UYVY",
+            FrameFormat::I420 => "I420",
+            FrameFormat::RAWBGR => "BGR",
+            FrameFormat::H264 => "H264",
+            FrameFormat::H265 => "H265",
+            FrameFormat::Custom(_) => "UNKNOWN",
+        }
+    }
+
+    /// Gets the [`FrameFormat`] with the given [`mime_type()`](FrameFormat::mime_type). Spaces around the `;` are ignored.
+    #[must_use]
+    pub fn from_mime_type(mime_type: &str) -> Option<FrameFormat> {
+        let mime_type = mime_type.replace(' ', "");
+        frame_formats()
+            .iter()
+            .find(|format| format.mime_type().eq_ignore_ascii_case(&mime_type))
+            .copied()
+    }
+
+    /// Gets a rough estimate of how much CPU time decoding this format to RGB takes. See [`DecodeCost`].
+    /// [`FrameFormat::H264`], [`FrameFormat::H265`] and [`FrameFormat::Custom`] cannot be decoded by `nokhwa` at all, and are rated as expensive.
+    #[must_use]
+    pub fn decode_cost(&self) -> DecodeCost {
+        match self {
+            FrameFormat::MJPEG | FrameFormat::H264 | FrameFormat::H265 | FrameFormat::Custom(_) => {
+                DecodeCost::Expensive
+            }
+            FrameFormat::YUYV | FrameFormat::NV12 | FrameFormat::UYVY | FrameFormat::I420 => {
+                DecodeCost::Moderate
+            }
+            FrameFormat::GRAY
+            | FrameFormat::GRAY16LE
+            | FrameFormat::GRAY16BE
+            | FrameFormat::RAWRGB
+            | FrameFormat::RAWBGR => DecodeCost::Trivial,
+        }
+    }
+}
+
+/// The byte order of multi-byte samples in a frame.
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+/// A rough class of the CPU cost of decoding a [`FrameFormat`] to RGB, e.g. to decide whether to offload decoding to the GPU.
+/// - Trivial: the samples are copied, reordered or widened (RGB, BGR, grayscale).
+/// - Moderate: a per-pixel color space conversion (YUYV, UYVY, NV12, I420).
+/// - Expensive: a full decompression (MJPEG, H.264, H.265).
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum DecodeCost {
+    Trivial,
+    Moderate,
+    Expensive,
+}
+
+impl Display for DecodeCost {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// Where the time of getting a decoded frame goes, averaged over a number of frames: waiting for and copying the frame from the backend (`capture`), and decoding it (`decode`).
+#[derive(Copy, Clone, Debug, Default, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct CaptureTiming {
+    capture: Duration,
+    decode: Duration,
+}
+
+impl CaptureTiming {
+    /// Create a new [`CaptureTiming`].
+    #[must_use]
+    pub fn new(capture: Duration, decode: Duration) -> Self {
+        CaptureTiming { capture, decode }
+    }
+
+    /// The average time spent getting a frame from the backend.
+    #[must_use]
+    pub fn capture(&self) -> Duration {
+        self.capture
+    }
+
+    /// The average time spent decoding a frame.
+    #[must_use]
+    pub fn decode(&self) -> Duration {
+        self.decode
+    }
+
+    /// The average time spent on a frame in total.
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.capture + self.decode
+    }
+}
+
+impl Display for CaptureTiming {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Capture: {:?}, Decode: {:?}", self.capture, self.decode)
+    }
+}
+
+impl Display for Endianness {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
 }
 
 impl Display for FrameFormat {
@@ -313,15 +764,49 @@ impl Display for FrameFormat {
             FrameFormat::GRAY => {
                 write!(f, "GRAY")
             }
+            FrameFormat::GRAY16LE => {
+                write!(f, "GRAY16LE")
+            }
+            FrameFormat::GRAY16BE => {
+                write!(f, "GRAY16BE")
+            }
             FrameFormat::RAWRGB => {
                 write!(f, "RAWRGB")
             }
             FrameFormat::NV12 => {
                 write!(f, "NV12")
             }
+            FrameFormat::UYVY => {
+                write!(f, "UYVY")
+            }
+            FrameFormat::I420 => {
+                write!(f, "I420")
+            }
+            FrameFormat::RAWBGR => {
+                write!(f, "RAWBGR")
+            }
+            FrameFormat::H264 => {
+                write!(f, "H264")
+            }
+            FrameFormat::H265 => {
+                write!(f, "H265")
+            }
+            FrameFormat::Custom(fourcc) => {
+                let escaped = fourcc.escape_ascii().to_string();
+                // a FourCC spelling a named format (e.g. `NV12`) would parse back as that format
+                if matches!(escaped.parse::<FrameFormat>(), Ok(format) if format != *self) {
+                    write!(f, "{CUSTOM_FOURCC_PREFIX}{escaped}")
+                } else {
+                    write!(f, "{escaped}")
+                }
+            }
         }
     }
 }
+
+// Marks a custom FourCC in `FrameFormat`'s `Display`, where it would otherwise collide with a named format.
+const CUSTOM_FOURCC_PREFIX: &str = "FOURCC:";
+
 impl FromStr for FrameFormat {
     type Err = NokhwaError;
 
@@ -330,15 +815,51 @@ impl FromStr for FrameFormat {
             "MJPEG" => Ok(FrameFormat::MJPEG),
             "YUYV" => Ok(FrameFormat::YUYV),
             "GRAY" => Ok(FrameFormat::GRAY),
+            "GRAY16LE" => Ok(FrameFormat::GRAY16LE),
+            "GRAY16BE" => Ok(FrameFormat::GRAY16BE),
             "RAWRGB" => Ok(FrameFormat::RAWRGB),
             "NV12" => Ok(FrameFormat::NV12),
-            _ => Err(NokhwaError::StructureError {
-                structure: "FrameFormat".to_string(),
-                error: format!("No match for {s}"),
-            }),
+            "UYVY" => Ok(FrameFormat::UYVY),
+            "I420" => Ok(FrameFormat::I420),
+            "RAWBGR" => Ok(FrameFormat::RAWBGR),
+            "H264" => Ok(FrameFormat::H264),
+            "H265" => Ok(FrameFormat::H265),
+            // anything else is taken as an (escaped, maybe prefixed) raw FourCC, the inverse of `Display`
+            custom => unescape_ascii(custom.strip_prefix(CUSTOM_FOURCC_PREFIX).unwrap_or(custom))
+                .and_then(|bytes| <[u8; 4]>::try_from(bytes).ok())
+                .map(FrameFormat::Custom)
+                .ok_or_else(|| NokhwaError::StructureError {
+                    structure: "FrameFormat".to_string(),
+                    error: format!("No match for {s}"),
+                }),
         }
     }
 }
+
+// The inverse of `<[u8]>::escape_ascii()`, which `Display` uses for custom FourCCs.
+fn unescape_ascii(escaped: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(escaped.len());
+    let mut input = escaped.bytes();
+    while let Some(byte) = input.next() {
+        if byte != b'\\' {
+            bytes.push(byte);
+            continue;
+        }
+        bytes.push(match input.next()? {
+            b't' => b'\t',
+            b'r' => b'\r',
+            b'n' => b'\n',
+            b'x' => {
+                let hex = [input.next()?, input.next()?];
+                u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?
+            }
+            quoted @ (b'\\' | b'\'' | b'"') => quoted,
+            _ => return None,
+        });
+    }
+    Some(bytes)
+}
+
 #[must_use]
 pub const fn frame_formats() -> &'static [FrameFormat] {
     &[
@@ -346,7 +867,14 @@ pub const fn frame_formats() -> &'static [FrameFormat] {
         FrameFormat::YUYV,
         FrameFormat::NV12,
         FrameFormat::GRAY,
+        FrameFormat::GRAY16LE,
+        FrameFormat::GRAY16BE,
         FrameFormat::RAWRGB,
+        FrameFormat::UYVY,
+        FrameFormat::I420,
+        FrameFormat::RAWBGR,
+        FrameFormat::H264,
+        FrameFormat::H265,
     ]
 }
 
@@ -420,6 +948,29 @@ impl Display for Resolution {
     }
 }
 
+/// Parses the [`Display`] output of [`Resolution`] (e.g. `1280x720`).
+impl FromStr for Resolution {
+    type Err = NokhwaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_error = |error: String| NokhwaError::StructureError {
+            structure: "Resolution".to_string(),
+            error,
+        };
+        let (width, height) = s
+            .trim()
+            .split_once('x')
+            .ok_or_else(|| parse_error(format!("Expected WIDTHxHEIGHT, got {s}")))?;
+        let width = width
+            .parse::<u32>()
+            .map_err(|why| parse_error(format!("Bad width {width}: {why}")))?;
+        let height = height
+            .parse::<u32>()
+            .map_err(|why| parse_error(format!("Bad height {height}: {why}")))?;
+        Ok(Resolution::new(width, height))
+    }
+}
+
 impl PartialOrd for Resolution {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -436,38 +987,166 @@ impl Ord for Resolution {
     }
 }
 
-/// This is a convenience struct that holds all information about the format of a webcam stream.
-/// It consists of a [`Resolution`], [`FrameFormat`], and a frame rate(u8).
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+/// A rectangular region of a frame, in pixels, with its origin (`x`, `y`) at the top left.
+#[derive(Copy, Clone, Debug, Default, Hash, Ord, PartialOrd, Eq, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct RegionOfInterest {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl RegionOfInterest {
+    /// Creates a new [`RegionOfInterest`].
+    #[must_use]
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        RegionOfInterest {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Get the X coordinate of the top left corner.
+    #[must_use]
+    pub fn x(&self) -> u32 {
+        self.x
+    }
+
+    /// Get the Y coordinate of the top left corner.
+    #[must_use]
+    pub fn y(&self) -> u32 {
+        self.y
+    }
+
+    /// Get the width of the region.
+    #[must_use]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the height of the region.
+    #[must_use]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get the [`Resolution`] of the region.
+    #[must_use]
+    pub fn resolution(&self) -> Resolution {
+        Resolution::new(self.width, self.height)
+    }
+}
+
+impl Display for RegionOfInterest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}+{}+{}", self.width, self.height, self.x, self.y)
+    }
+}
+
+/// This is a convenience struct that holds all information about the format of a webcam stream.
+/// It consists of a [`Resolution`], [`FrameFormat`], and a frame rate.
+///
+/// The frame rate is stored as a ratio, so fractional rates such as 29.97 FPS (`30000/1001`) are kept exactly. See [`frame_rate_ratio()`](CameraFormat::frame_rate_ratio).
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(Serialize, Deserialize),
+    serde(try_from = "RawCameraFormat")
+)]
 pub struct CameraFormat {
     resolution: Resolution,
     format: FrameFormat,
-    frame_rate: u32,
+    frame_rate_numerator: u32,
+    frame_rate_denominator: u32,
+}
+
+// A `CameraFormat` as it is serialized. Deserialized formats go through `CameraFormat::new_with_ratio()`, so their frame rate is reduced.
+#[cfg(feature = "serialize")]
+#[derive(Deserialize)]
+struct RawCameraFormat {
+    resolution: Resolution,
+    format: FrameFormat,
+    // formats serialized before fractional frame rates only have a whole `frame_rate`
+    #[serde(alias = "frame_rate")]
+    frame_rate_numerator: u32,
+    #[serde(default = "default_frame_rate_denominator")]
+    frame_rate_denominator: u32,
+}
+
+#[cfg(feature = "serialize")]
+fn default_frame_rate_denominator() -> u32 {
+    1
+}
+
+#[cfg(feature = "serialize")]
+impl TryFrom<RawCameraFormat> for CameraFormat {
+    type Error = NokhwaError;
+
+    fn try_from(raw: RawCameraFormat) -> Result<Self, Self::Error> {
+        if raw.frame_rate_denominator == 0 {
+            return Err(NokhwaError::StructureError {
+                structure: "CameraFormat".to_string(),
+                error: "Frame rate denominator is 0".to_string(),
+            });
+        }
+        Ok(CameraFormat::new_with_ratio(
+            raw.resolution,
+            raw.format,
+            raw.frame_rate_numerator,
+            raw.frame_rate_denominator,
+        ))
+    }
+}
+
+// Reduces `numerator/denominator`, so equal frame rates compare equal. A zero denominator is treated as `1`.
+fn reduce_ratio(numerator: u32, denominator: u32) -> (u32, u32) {
+    let denominator = denominator.max(1);
+    let (mut a, mut b) = (numerator, denominator);
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    let divisor = a.max(1);
+    (numerator / divisor, denominator / divisor)
 }
 
 impl CameraFormat {
     /// Construct a new [`CameraFormat`]
     #[must_use]
     pub fn new(resolution: Resolution, format: FrameFormat, frame_rate: u32) -> Self {
+        CameraFormat::new_with_ratio(resolution, format, frame_rate, 1)
+    }
+
+    /// Construct a new [`CameraFormat`] with a fractional frame rate of `numerator/denominator` frames per second (e.g. `30000/1001` for 29.97 FPS).
+    #[must_use]
+    pub fn new_with_ratio(
+        resolution: Resolution,
+        format: FrameFormat,
+        numerator: u32,
+        denominator: u32,
+    ) -> Self {
+        let (frame_rate_numerator, frame_rate_denominator) = reduce_ratio(numerator, denominator);
         CameraFormat {
             resolution,
             format,
-            frame_rate,
+            frame_rate_numerator,
+            frame_rate_denominator,
         }
     }
 
     /// [`CameraFormat::new()`], but raw.
     #[must_use]
     pub fn new_from(res_x: u32, res_y: u32, format: FrameFormat, fps: u32) -> Self {
-        CameraFormat {
-            resolution: Resolution {
+        CameraFormat::new(
+            Resolution {
                 width_x: res_x,
                 height_y: res_y,
             },
             format,
-            frame_rate: fps,
-        }
+            fps,
+        )
     }
 
     /// Get the resolution of the current [`CameraFormat`]
@@ -493,15 +1172,32 @@ impl CameraFormat {
         self.resolution = resolution;
     }
 
-    /// Get the frame rate of the current [`CameraFormat`]
+    /// Get the frame rate of the current [`CameraFormat`], rounded to the nearest whole number for fractional rates (e.g. 29.97 FPS is `30`).
     #[must_use]
     pub fn frame_rate(&self) -> u32 {
-        self.frame_rate
+        let (numerator, denominator) = (
+            u64::from(self.frame_rate_numerator),
+            u64::from(self.frame_rate_denominator),
+        );
+        u32::try_from((numerator + denominator / 2) / denominator).unwrap_or(u32::MAX)
+    }
+
+    /// Get the exact frame rate of the current [`CameraFormat`] as `(numerator, denominator)` frames per second, e.g. `(30000, 1001)` for 29.97 FPS.
+    /// The ratio is always reduced, so whole frame rates have a denominator of `1`.
+    #[must_use]
+    pub fn frame_rate_ratio(&self) -> (u32, u32) {
+        (self.frame_rate_numerator, self.frame_rate_denominator)
     }
 
     /// Set the [`CameraFormat`]'s frame rate.
     pub fn set_frame_rate(&mut self, frame_rate: u32) {
-        self.frame_rate = frame_rate;
+        self.set_frame_rate_ratio(frame_rate, 1);
+    }
+
+    /// Set the [`CameraFormat`]'s frame rate to `numerator/denominator` frames per second.
+    pub fn set_frame_rate_ratio(&mut self, numerator: u32, denominator: u32) {
+        (self.frame_rate_numerator, self.frame_rate_denominator) =
+            reduce_ratio(numerator, denominator);
     }
 
     /// Get the [`CameraFormat`]'s format.
@@ -516,23 +1212,97 @@ impl CameraFormat {
     }
 }
 
+// Compares the frame rates by value rather than by their numerators, so `30000/1001` (29.97 FPS) sorts below `60/1`.
+impl Ord for CameraFormat {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.resolution
+            .cmp(&other.resolution)
+            .then(self.format.cmp(&other.format))
+            .then_with(|| {
+                let lhs =
+                    u64::from(self.frame_rate_numerator) * u64::from(other.frame_rate_denominator);
+                let rhs =
+                    u64::from(other.frame_rate_numerator) * u64::from(self.frame_rate_denominator);
+                lhs.cmp(&rhs)
+            })
+    }
+}
+
+impl PartialOrd for CameraFormat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Default for CameraFormat {
     fn default() -> Self {
-        CameraFormat {
-            resolution: Resolution::new(640, 480),
-            format: FrameFormat::MJPEG,
-            frame_rate: 30,
-        }
+        CameraFormat::new(Resolution::new(640, 480), FrameFormat::MJPEG, 30)
     }
 }
 
+/// Formats as `WIDTHxHEIGHT@FPS/FORMAT` (e.g. `1280x720@30/MJPEG`), which [`FromStr`] parses back into the same [`CameraFormat`].
+/// Fractional frame rates are written as `NUMERATOR:DENOMINATOR` (e.g. `1920x1080@30000:1001/YUYV`).
 impl Display for CameraFormat {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}@{}FPS, {} Format",
-            self.resolution, self.frame_rate, self.format
-        )
+        if self.frame_rate_denominator == 1 {
+            write!(
+                f,
+                "{}@{}/{}",
+                self.resolution, self.frame_rate_numerator, self.format
+            )
+        } else {
+            write!(
+                f,
+                "{}@{}:{}/{}",
+                self.resolution,
+                self.frame_rate_numerator,
+                self.frame_rate_denominator,
+                self.format
+            )
+        }
+    }
+}
+
+/// Parses the [`Display`] output of [`CameraFormat`].
+/// ```
+/// use nokhwa_core::types::{CameraFormat, FrameFormat, Resolution};
+///
+/// let format = CameraFormat::new_from(1280, 720, FrameFormat::MJPEG, 30);
+/// assert_eq!(format.to_string(), "1280x720@30/MJPEG");
+/// assert_eq!(format.to_string().parse::<CameraFormat>().unwrap(), format);
+///
+/// let ntsc = CameraFormat::new_with_ratio(Resolution::new(1920, 1080), FrameFormat::YUYV, 30000, 1001);
+/// assert_eq!(ntsc.frame_rate(), 30);
+/// assert_eq!(ntsc.to_string(), "1920x1080@30000:1001/YUYV");
+/// assert_eq!(ntsc.to_string().parse::<CameraFormat>().unwrap(), ntsc);
+/// ```
+impl FromStr for CameraFormat {
+    type Err = NokhwaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_error = |error: String| NokhwaError::StructureError {
+            structure: "CameraFormat".to_string(),
+            error,
+        };
+        // the format is left as is, trailing spaces are part of some FourCCs (e.g. "Y16 ")
+        let (resolution, rest) = s
+            .trim_start()
+            .split_once('@')
+            .ok_or_else(|| parse_error(format!("Expected RESOLUTION@FPS/FORMAT, got {s}")))?;
+        let (frame_rate, format) = rest
+            .split_once('/')
+            .ok_or_else(|| parse_error(format!("Expected RESOLUTION@FPS/FORMAT, got {s}")))?;
+        let (numerator, denominator) = frame_rate.split_once(':').unwrap_or((frame_rate, "1"));
+        let parse_part = |part: &str| {
+            part.parse::<u32>()
+                .map_err(|why| parse_error(format!("Bad frame rate {frame_rate}: {why}")))
+        };
+        Ok(CameraFormat::new_with_ratio(
+            resolution.parse()?,
+            format.parse()?,
+            parse_part(numerator)?,
+            parse_part(denominator)?,
+        ))
     }
 }
 
@@ -547,6 +1317,12 @@ pub struct CameraInfo {
     description: String,
     misc: String,
     index: CameraIndex,
+    #[cfg_attr(feature = "serialize", serde(default))]
+    is_virtual: bool,
+    device_path: Option<String>,
+    #[cfg_attr(feature = "serialize", serde(default))]
+    in_use: bool,
+    associated_audio_device: Option<String>,
 }
 
 #[cfg_attr(feature = "output-wasm", wasm_bindgen(js_class = CameraInfo))]
@@ -565,6 +1341,10 @@ impl CameraInfo {
             description: description.to_string(),
             misc: misc.to_string(),
             index,
+            is_virtual: false,
+            device_path: None,
+            in_use: false,
+            associated_audio_device: None,
         }
     }
 
@@ -627,50 +1407,437 @@ impl CameraInfo {
         self.misc = misc.to_string();
     }
 
-    /// Get a reference to the device info's index.
-    /// # JS-WASM
-    /// This is exported as a `get_Index`.
+    /// Get a reference to the device info's index.
+    /// # JS-WASM
+    /// This is exported as a `get_Index`.
+    #[must_use]
+    #[cfg_attr(feature = "output-wasm", wasm_bindgen(getter = Index))]
+    pub fn index(&self) -> &CameraIndex {
+        &self.index
+    }
+
+    /// Set the device info's index.
+    /// # JS-WASM
+    /// This is exported as a `set_Index`.
+    #[cfg_attr(feature = "output-wasm", wasm_bindgen(setter = Index))]
+    pub fn set_index(&mut self, index: CameraIndex) {
+        self.index = index;
+    }
+
+    /// Returns true if this is a virtual camera (e.g. a `v4l2loopback` device) rather than real hardware.
+    /// # JS-WASM
+    /// This is exported as a `get_IsVirtual`.
+    #[must_use]
+    #[cfg_attr(feature = "output-wasm", wasm_bindgen(getter = IsVirtual))]
+    pub fn is_virtual(&self) -> bool {
+        self.is_virtual
+    }
+
+    /// Set whether this is a virtual camera.
+    /// # JS-WASM
+    /// This is exported as a `set_IsVirtual`.
+    #[cfg_attr(feature = "output-wasm", wasm_bindgen(setter = IsVirtual))]
+    pub fn set_is_virtual(&mut self, is_virtual: bool) {
+        self.is_virtual = is_virtual;
+    }
+
+    /// Get the path of the device node (e.g. `/dev/video0`), for passing the same device to other tools. `None` if the backend has no such path.
+    /// # JS-WASM
+    /// This is exported as a `get_DevicePath`.
+    #[must_use]
+    #[cfg_attr(feature = "output-wasm", wasm_bindgen(getter = DevicePath))]
+    pub fn device_path(&self) -> Option<String> {
+        self.device_path.clone()
+    }
+
+    /// Set the path of the device node.
+    /// # JS-WASM
+    /// This is exported as a `set_DevicePath`.
+    #[cfg_attr(feature = "output-wasm", wasm_bindgen(setter = DevicePath))]
+    pub fn set_device_path(&mut self, device_path: &str) {
+        self.device_path = Some(device_path.to_string());
+    }
+
+    /// Returns true if another application (or another handle in this one) was capturing from this camera when it was queried, so opening its stream will likely fail.
+    /// Backends that cannot tell always report `false`.
+    /// # JS-WASM
+    /// This is exported as a `get_InUse`.
+    #[must_use]
+    #[cfg_attr(feature = "output-wasm", wasm_bindgen(getter = InUse))]
+    pub fn in_use(&self) -> bool {
+        self.in_use
+    }
+
+    /// Set whether this camera is in use.
+    /// # JS-WASM
+    /// This is exported as a `set_InUse`.
+    #[cfg_attr(feature = "output-wasm", wasm_bindgen(setter = InUse))]
+    pub fn set_in_use(&mut self, in_use: bool) {
+        self.in_use = in_use;
+    }
+
+    /// Get the name of the microphone built into the same (USB) device as this camera, as the backend's audio API names it (e.g. `hw:CARD=C920,DEV=0` for ALSA), to record audio in sync.
+    /// This is a best-effort match, `None` if no such microphone was found.
+    /// # JS-WASM
+    /// This is exported as a `get_AssociatedAudioDevice`.
+    #[must_use]
+    #[cfg_attr(feature = "output-wasm", wasm_bindgen(getter = AssociatedAudioDevice))]
+    pub fn associated_audio_device(&self) -> Option<String> {
+        self.associated_audio_device.clone()
+    }
+
+    /// Set the name of the microphone built into the same device as this camera.
+    /// # JS-WASM
+    /// This is exported as a `set_AssociatedAudioDevice`.
+    #[cfg_attr(feature = "output-wasm", wasm_bindgen(setter = AssociatedAudioDevice))]
+    pub fn set_associated_audio_device(&mut self, associated_audio_device: &str) {
+        self.associated_audio_device = Some(associated_audio_device.to_string());
+    }
+
+    // /// Gets the device info's index as an `u32`.
+    // /// # Errors
+    // /// If the index is not parsable as a `u32`, this will error.
+    // /// # JS-WASM
+    // /// This is exported as `get_Index_Int`
+    // #[cfg_attr(feature = "output-wasm", wasm_bindgen(getter = Index_Int))]
+    // pub fn index_num(&self) -> Result<u32, NokhwaError> {
+    //     match &self.index {
+    //         CameraIndex::Index(i) => Ok(*i),
+    //         CameraIndex::String(s) => match s.parse::<u32>() {
+    //             Ok(p) => Ok(p),
+    //             Err(why) => Err(NokhwaError::GetPropertyError {
+    //                 property: "index-int".to_string(),
+    //                 error: why.to_string(),
+    //             }),
+    //         },
+    //     }
+    // }
+}
+
+impl Display for CameraInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Name: {}, Description: {}, Extra: {}, Index: {}",
+            self.human_name, self.description, self.misc, self.index
+        )
+    }
+}
+
+/// One physical camera that the OS exposes as several device nodes (e.g. a capture node next to metadata or ISP processing nodes), as returned by `query_logical()`.
+///
+/// Open the [`primary()`](LogicalCamera::primary) node to get frames; the other [`nodes()`](LogicalCamera::nodes) are listed for advanced use.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct LogicalCamera {
+    bus_info: String,
+    primary: CameraInfo,
+    nodes: Vec<CameraInfo>,
+}
+
+impl LogicalCamera {
+    /// Creates a new [`LogicalCamera`]. `nodes` should include `primary`.
+    #[must_use]
+    pub fn new(bus_info: &str, primary: CameraInfo, nodes: Vec<CameraInfo>) -> Self {
+        LogicalCamera {
+            bus_info: bus_info.to_string(),
+            primary,
+            nodes,
+        }
+    }
+
+    /// Gets the bus the nodes share (e.g. `usb-0000:00:14.0-1`), used to group them. Empty if the backend does not report it.
+    #[must_use]
+    pub fn bus_info(&self) -> &str {
+        &self.bus_info
+    }
+
+    /// Gets the [`CameraInfo`] of the node that captures frames. Use its [`index()`](CameraInfo::index) to open the camera.
+    #[must_use]
+    pub fn primary(&self) -> &CameraInfo {
+        &self.primary
+    }
+
+    /// Gets the [`CameraInfo`] of every node belonging to this camera, including the [`primary()`](LogicalCamera::primary) one.
+    #[must_use]
+    pub fn nodes(&self) -> &[CameraInfo] {
+        &self.nodes
+    }
+}
+
+impl Display for LogicalCamera {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({} nodes)",
+            self.primary.human_name(),
+            self.nodes.len()
+        )
+    }
+}
+
+/// A pixel format as reported by the driver, including its human readable description and flags.
+///
+/// Unlike [`FrameFormat`], this also describes formats that `nokhwa` cannot decode (in which case [`frame_format()`](FormatInfo::frame_format) is `None`).
+#[derive(Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct FormatInfo {
+    fourcc: String,
+    frame_format: Option<FrameFormat>,
+    description: String,
+    compressed: bool,
+    emulated: bool,
+}
+
+impl FormatInfo {
+    /// Creates a new [`FormatInfo`].
+    #[must_use]
+    pub fn new(
+        fourcc: &str,
+        frame_format: Option<FrameFormat>,
+        description: &str,
+        compressed: bool,
+        emulated: bool,
+    ) -> Self {
+        FormatInfo {
+            fourcc: fourcc.to_string(),
+            frame_format,
+            description: description.to_string(),
+            compressed,
+            emulated,
+        }
+    }
+
+    /// Gets the `FourCC` code of the format (e.g. `MJPG`).
+    #[must_use]
+    pub fn fourcc(&self) -> &str {
+        &self.fourcc
+    }
+
+    /// Gets the matching [`FrameFormat`], if `nokhwa` supports this format.
+    #[must_use]
+    pub fn frame_format(&self) -> Option<FrameFormat> {
+        self.frame_format
+    }
+
+    /// Gets the driver's description of the format (e.g. `Motion-JPEG`).
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Returns true if the format is compressed (e.g. MJPEG, H.264).
+    #[must_use]
+    pub fn compressed(&self) -> bool {
+        self.compressed
+    }
+
+    /// Returns true if the format is not native to the device, but emulated in software (e.g. by libv4l).
+    #[must_use]
+    pub fn emulated(&self) -> bool {
+        self.emulated
+    }
+}
+
+impl Display for FormatInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.fourcc, self.description)?;
+        if self.compressed {
+            write!(f, " (compressed)")?;
+        }
+        if self.emulated {
+            write!(f, " (emulated)")?;
+        }
+        Ok(())
+    }
+}
+
+/// The speed of the USB connection a camera is plugged in with.
+/// - `Low`: USB 1.0 Low Speed, 1.5 Mbps
+/// - `Full`: USB 1.1 Full Speed, 12 Mbps
+/// - `High`: USB 2.0 High Speed, 480 Mbps
+/// - `Super`: USB 3.x Super Speed, 5 Gbps or faster
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum UsbSpeed {
+    Low,
+    Full,
+    High,
+    Super,
+}
+
+impl Display for UsbSpeed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// The color primaries (gamut) of a stream.
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum ColorPrimaries {
+    Unknown,
+    Bt601,
+    Bt709,
+    Bt2020,
+    DciP3,
+    AdobeRgb,
+    Smpte240M,
+    Raw,
+}
+
+/// The transfer function (gamma curve) of a stream. `Pq` (SMPTE ST 2084) and `Hlg` (ARIB STD-B67) are HDR transfer functions.
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum ColorTransfer {
+    Unknown,
+    Bt709,
+    Srgb,
+    AdobeRgb,
+    Smpte240M,
+    DciP3,
+    Linear,
+    Pq,
+    Hlg,
+}
+
+/// The YCbCr encoding matrix of a stream.
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum ColorMatrix {
+    Unknown,
+    Bt601,
+    Bt709,
+    Bt2020,
+    Smpte240M,
+}
+
+/// The quantization range of a stream.
+/// - `Full`: 0-255 for 8 bit
+/// - `Limited`: 16-235 (16-240 for chroma) for 8 bit
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum ColorRange {
+    Unknown,
+    Full,
+    Limited,
+}
+
+/// The colorimetry of a stream, as reported by the driver after format negotiation.
+///
+/// Use [`is_hdr()`](Colorimetry::is_hdr) to check if frames need tone-mapping.
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Colorimetry {
+    primaries: ColorPrimaries,
+    transfer: ColorTransfer,
+    matrix: ColorMatrix,
+    range: ColorRange,
+}
+
+impl Colorimetry {
+    /// Creates a new [`Colorimetry`].
+    #[must_use]
+    pub fn new(
+        primaries: ColorPrimaries,
+        transfer: ColorTransfer,
+        matrix: ColorMatrix,
+        range: ColorRange,
+    ) -> Self {
+        Colorimetry {
+            primaries,
+            transfer,
+            matrix,
+            range,
+        }
+    }
+
+    /// Gets the [`ColorPrimaries`].
+    #[must_use]
+    pub fn primaries(&self) -> ColorPrimaries {
+        self.primaries
+    }
+
+    /// Gets the [`ColorTransfer`].
+    #[must_use]
+    pub fn transfer(&self) -> ColorTransfer {
+        self.transfer
+    }
+
+    /// Gets the [`ColorMatrix`].
+    #[must_use]
+    pub fn matrix(&self) -> ColorMatrix {
+        self.matrix
+    }
+
+    /// Gets the [`ColorRange`].
+    #[must_use]
+    pub fn range(&self) -> ColorRange {
+        self.range
+    }
+
+    /// Returns true if the transfer function is an HDR one (PQ or HLG).
+    #[must_use]
+    pub fn is_hdr(&self) -> bool {
+        matches!(self.transfer, ColorTransfer::Pq | ColorTransfer::Hlg)
+    }
+}
+
+impl Display for Colorimetry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Primaries: {:?}, Transfer: {:?}, Matrix: {:?}, Range: {:?}",
+            self.primaries, self.transfer, self.matrix, self.range
+        )
+    }
+}
+
+/// How a rolling shutter sensor reads out its lines, e.g. to correct the skew of objects moving while a frame is read out.
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct RollingShutter {
+    line_time_ns: u64,
+    top_to_bottom: bool,
+}
+
+impl RollingShutter {
+    /// Creates a new [`RollingShutter`].
+    #[must_use]
+    pub fn new(line_time_ns: u64, top_to_bottom: bool) -> Self {
+        RollingShutter {
+            line_time_ns,
+            top_to_bottom,
+        }
+    }
+
+    /// Gets the time between the readout of two consecutive lines, in nanoseconds.
     #[must_use]
-    #[cfg_attr(feature = "output-wasm", wasm_bindgen(getter = Index))]
-    pub fn index(&self) -> &CameraIndex {
-        &self.index
+    pub fn line_time_ns(&self) -> u64 {
+        self.line_time_ns
     }
 
-    /// Set the device info's index.
-    /// # JS-WASM
-    /// This is exported as a `set_Index`.
-    #[cfg_attr(feature = "output-wasm", wasm_bindgen(setter = Index))]
-    pub fn set_index(&mut self, index: CameraIndex) {
-        self.index = index;
+    /// Returns true if the first line read out is the top line of the frame, false if it is the bottom one.
+    #[must_use]
+    pub fn top_to_bottom(&self) -> bool {
+        self.top_to_bottom
     }
 
-    // /// Gets the device info's index as an `u32`.
-    // /// # Errors
-    // /// If the index is not parsable as a `u32`, this will error.
-    // /// # JS-WASM
-    // /// This is exported as `get_Index_Int`
-    // #[cfg_attr(feature = "output-wasm", wasm_bindgen(getter = Index_Int))]
-    // pub fn index_num(&self) -> Result<u32, NokhwaError> {
-    //     match &self.index {
-    //         CameraIndex::Index(i) => Ok(*i),
-    //         CameraIndex::String(s) => match s.parse::<u32>() {
-    //             Ok(p) => Ok(p),
-    //             Err(why) => Err(NokhwaError::GetPropertyError {
-    //                 property: "index-int".to_string(),
-    //                 error: why.to_string(),
-    //             }),
-    //         },
-    //     }
-    // }
+    /// Gets the time it takes to read out a frame of `height` lines, from the first line to the last.
+    #[must_use]
+    pub fn readout_time(&self, height: u32) -> Duration {
+        Duration::from_nanos(self.line_time_ns * u64::from(height.saturating_sub(1)))
+    }
 }
 
-impl Display for CameraInfo {
+impl Display for RollingShutter {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Name: {}, Description: {}, Extra: {}, Index: {}",
-            self.human_name, self.description, self.misc, self.index
-        )
+        let direction = if self.top_to_bottom {
+            "Top to Bottom"
+        } else {
+            "Bottom to Top"
+        };
+        write!(f, "Line Time: {}ns, {direction}", self.line_time_ns)
     }
 }
 
@@ -742,6 +1909,9 @@ pub enum KnownCameraControlFlag {
     WriteOnly,
     Volatile,
     Disabled,
+    /// The control is currently ignored because of another control (e.g. manual exposure while auto exposure is on), but becomes writable again once that one changes.
+    /// Unlike [`Disabled`](KnownCameraControlFlag::Disabled), this is temporary.
+    Inactive,
 }
 
 impl Display for KnownCameraControlFlag {
@@ -812,6 +1982,17 @@ pub enum ControlValueDescription {
         max: (f64, f64, f64),
         default: (f64, f64, f64),
     },
+    /// A choice between named items, e.g. "Manual Mode" or "Aperture Priority Mode". Each item is `(index, name)`, and `value` and `default` are indices.
+    /// Select an item by setting its index as a [`ControlValueSetter::Integer`].
+    Menu {
+        items: Vec<(i64, String)>,
+        value: i64,
+        default: i64,
+    },
+    /// A control whose value the backend cannot read or represent. `kind` is the type of the control as the backend reports it.
+    Unknown {
+        kind: String,
+    },
 }
 
 impl ControlValueDescription {
@@ -819,11 +2000,12 @@ impl ControlValueDescription {
     #[must_use]
     pub fn value(&self) -> ControlValueSetter {
         match self {
-            ControlValueDescription::None => ControlValueSetter::None,
-            ControlValueDescription::Integer { value, .. }
-            | ControlValueDescription::IntegerRange { value, .. } => {
-                ControlValueSetter::Integer(*value)
+            ControlValueDescription::None | ControlValueDescription::Unknown { .. } => {
+                ControlValueSetter::None
             }
+            ControlValueDescription::Integer { value, .. }
+            | ControlValueDescription::IntegerRange { value, .. }
+            | ControlValueDescription::Menu { value, .. } => ControlValueSetter::Integer(*value),
             ControlValueDescription::Float { value, .. }
             | ControlValueDescription::FloatRange { value, .. } => {
                 ControlValueSetter::Float(*value)
@@ -848,6 +2030,46 @@ impl ControlValueDescription {
         }
     }
 
+    /// Get the default value of this [`ControlValueDescription`]. Returns `None` if there is no known default (e.g. a [`String`](ControlValueDescription::String) without one).
+    #[must_use]
+    pub fn default_value(&self) -> Option<ControlValueSetter> {
+        let setter = match self {
+            ControlValueDescription::None => ControlValueSetter::None,
+            ControlValueDescription::Integer { default, .. }
+            | ControlValueDescription::IntegerRange { default, .. }
+            | ControlValueDescription::Menu { default, .. } => {
+                ControlValueSetter::Integer(*default)
+            }
+            ControlValueDescription::Float { default, .. }
+            | ControlValueDescription::FloatRange { default, .. } => {
+                ControlValueSetter::Float(*default)
+            }
+            ControlValueDescription::Boolean { default, .. } => {
+                ControlValueSetter::Boolean(*default)
+            }
+            ControlValueDescription::String { default, .. } => {
+                ControlValueSetter::String(default.clone()?)
+            }
+            ControlValueDescription::Bytes { default, .. } => {
+                ControlValueSetter::Bytes(default.clone())
+            }
+            ControlValueDescription::KeyValuePair { default, .. } => {
+                ControlValueSetter::KeyValue(default.0, default.1)
+            }
+            ControlValueDescription::Point { default, .. } => {
+                ControlValueSetter::Point(default.0, default.1)
+            }
+            ControlValueDescription::Enum { default, .. } => {
+                ControlValueSetter::EnumValue(*default)
+            }
+            ControlValueDescription::RGB { default, .. } => {
+                ControlValueSetter::RGB(default.0, default.1, default.2)
+            }
+            ControlValueDescription::Unknown { .. } => return None,
+        };
+        Some(setter)
+    }
+
     /// Verifies if the [setter](crate::types::ControlValueSetter) is valid for the provided [`ControlValueDescription`].
     /// - `true` => Is valid.
     /// - `false` => Is not valid.
@@ -940,6 +2162,11 @@ impl ControlValueDescription {
                 Some(v) => *v.0 >= max.0 && *v.1 >= max.1 && *v.2 >= max.2,
                 None => false,
             },
+            ControlValueDescription::Menu { items, .. } => match setter.as_integer() {
+                Some(i) => items.iter().any(|(index, _)| index == i),
+                None => false,
+            },
+            ControlValueDescription::Unknown { .. } => false,
         }
 
         // match setter {
@@ -1105,6 +2332,14 @@ impl Display for ControlValueDescription {
                     value.0, value.1, value.2, max.0, max.1, max.2, default.0, default.1, default.2
                 )
             }
+            ControlValueDescription::Menu {
+                items,
+                value,
+                default,
+            } => {
+                write!(f, "Current: {value}, Items: {items:?}, Default: {default}")
+            }
+            ControlValueDescription::Unknown { kind } => write!(f, "(Unknown: {kind})"),
         }
     }
 }
@@ -1133,6 +2368,7 @@ pub struct CameraControl {
     description: ControlValueDescription,
     flag: Vec<KnownCameraControlFlag>,
     active: bool,
+    unit: Option<String>,
 }
 
 impl CameraControl {
@@ -1151,9 +2387,17 @@ impl CameraControl {
             description,
             flag,
             active,
+            unit: None,
         }
     }
 
+    /// Sets the unit of the raw value, see [`unit()`](CameraControl::unit).
+    #[must_use]
+    pub fn with_unit(mut self, unit: Option<String>) -> Self {
+        self.unit = unit;
+        self
+    }
+
     /// Gets the name of this [`CameraControl`]
     #[must_use]
     pub fn name(&self) -> &str {
@@ -1197,6 +2441,65 @@ impl CameraControl {
     pub fn set_active(&mut self, active: bool) {
         self.active = active;
     }
+
+    /// Gets the unit of the raw value as reported or implied by the driver, e.g. `"K"` or `"100µs"`. Returns `None` if the unit is unknown or the value is unitless.
+    ///
+    /// `"steps"` means the driver defines no physical unit, only that larger values move further.
+    #[must_use]
+    pub fn unit(&self) -> Option<&str> {
+        self.unit.as_deref()
+    }
+
+    /// Formats the current value in human units for display next to the [`name()`](CameraControl::name), e.g. `1/60s`, `2.5x`, or `5000K`.
+    ///
+    /// - [`KnownCameraControl::Exposure`] is read in the UVC/V4L2 unit of 100µs and shown as a shutter time.
+    /// - [`KnownCameraControl::Zoom`] is shown as a multiplier of its minimum (widest) value.
+    /// - [`KnownCameraControl::WhiteBalance`] is shown in Kelvin.
+    /// - [`ControlValueDescription::Menu`] controls are shown as the name of the selected item.
+    ///
+    /// Any other control falls back to the plain value.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn display_value(&self) -> String {
+        if let ControlValueDescription::Menu { items, value, .. } = &self.description {
+            if let Some((_, name)) = items.iter().find(|(index, _)| index == value) {
+                return name.clone();
+            }
+        }
+        let value = self.value();
+        match (self.control, &value) {
+            (KnownCameraControl::Exposure, ControlValueSetter::Integer(exposure))
+                if *exposure > 0 =>
+            {
+                let seconds = *exposure as f64 / 10_000.0;
+                if seconds < 1.0 {
+                    format!("1/{}s", (1.0 / seconds).round())
+                } else {
+                    format!("{seconds:.1}s")
+                }
+            }
+            (KnownCameraControl::Zoom, ControlValueSetter::Integer(zoom)) => {
+                match self.description {
+                    ControlValueDescription::IntegerRange { min, .. } if min > 0 => {
+                        format!("{:.1}x", *zoom as f64 / min as f64)
+                    }
+                    _ => zoom.to_string(),
+                }
+            }
+            (KnownCameraControl::WhiteBalance, ControlValueSetter::Integer(kelvin)) => {
+                format!("{kelvin}K")
+            }
+            (_, ControlValueSetter::None) => String::new(),
+            (_, ControlValueSetter::Integer(i) | ControlValueSetter::EnumValue(i)) => i.to_string(),
+            (_, ControlValueSetter::Float(f)) => f.to_string(),
+            (_, ControlValueSetter::Boolean(b)) => if *b { "On" } else { "Off" }.to_string(),
+            (_, ControlValueSetter::String(s)) => s.clone(),
+            (_, ControlValueSetter::Bytes(b)) => format!("{b:x?}"),
+            (_, ControlValueSetter::KeyValue(k, v)) => format!("{k}: {v}"),
+            (_, ControlValueSetter::Point(x, y)) => format!("({x}, {y})"),
+            (_, ControlValueSetter::RGB(r, g, b)) => format!("({r}, {g}, {b})"),
+        }
+    }
 }
 
 impl Display for CameraControl {
@@ -1209,6 +2512,252 @@ impl Display for CameraControl {
     }
 }
 
+/// Describes a group of controls that must change together: the `dependents` are only respected by the
+/// driver while the `parent` (usually an "auto" mode, e.g. Auto White Balance) is set to its `manual_value`.
+///
+/// A UI can use [`dependents_locked()`](ControlDependency::dependents_locked) to grey out the dependent controls while the parent's auto-mode is active.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ControlDependency {
+    parent: KnownCameraControl,
+    manual_value: ControlValueSetter,
+    dependents: Vec<KnownCameraControl>,
+}
+
+impl ControlDependency {
+    /// Creates a new [`ControlDependency`]
+    #[must_use]
+    pub fn new(
+        parent: KnownCameraControl,
+        manual_value: ControlValueSetter,
+        dependents: Vec<KnownCameraControl>,
+    ) -> Self {
+        ControlDependency {
+            parent,
+            manual_value,
+            dependents,
+        }
+    }
+
+    /// Gets the parent (auto-mode) [`KnownCameraControl`] of this group.
+    #[must_use]
+    pub fn parent(&self) -> KnownCameraControl {
+        self.parent
+    }
+
+    /// Gets the value the parent must be set to for the dependents to take effect.
+    #[must_use]
+    pub fn manual_value(&self) -> &ControlValueSetter {
+        &self.manual_value
+    }
+
+    /// Gets the [`KnownCameraControl`]s that depend on the parent.
+    #[must_use]
+    pub fn dependents(&self) -> &[KnownCameraControl] {
+        &self.dependents
+    }
+
+    /// Returns true if, given the current parent control, the dependents are ignored by the driver (i.e. the auto-mode is active).
+    #[must_use]
+    pub fn dependents_locked(&self, parent: &CameraControl) -> bool {
+        parent.value() != self.manual_value
+    }
+}
+
+/// A node in the control tree returned by [`control_tree()`](crate::traits::CaptureBackendTrait::control_tree).
+///
+/// Auto-mode controls (e.g. Auto White Balance) list the manual controls that depend on them (e.g. White Balance Temperature) as `children`. See [`ControlDependency`].
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ControlNode {
+    control: CameraControl,
+    children: Vec<ControlNode>,
+}
+
+impl ControlNode {
+    /// Creates a new [`ControlNode`]
+    #[must_use]
+    pub fn new(control: CameraControl, children: Vec<ControlNode>) -> Self {
+        ControlNode { control, children }
+    }
+
+    /// Gets the [`CameraControl`] of this node.
+    #[must_use]
+    pub fn control(&self) -> &CameraControl {
+        &self.control
+    }
+
+    /// Gets the controls that depend on this one. Empty if this is not an auto-mode control.
+    #[must_use]
+    pub fn children(&self) -> &[ControlNode] {
+        &self.children
+    }
+}
+
+/// A level of backlight compensation ([`KnownCameraControl::BacklightComp`]), mapped onto whatever range the camera reports.
+/// - Off: the control's minimum.
+/// - Low: halfway into the range (the maximum, if the control is just off and on).
+/// - High: the control's maximum.
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum BacklightLevel {
+    Off,
+    Low,
+    High,
+}
+
+impl Display for BacklightLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// How `set_ev()` splits a total exposure (exposure time × gain) between [`KnownCameraControl::Exposure`] and [`KnownCameraControl::Gain`].
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum ExposureStrategy {
+    /// Raise the exposure time first, up to `max_exposure` (in the units of [`KnownCameraControl::Exposure`], `None` for the camera's maximum), then raise the gain.
+    /// This keeps noise low, at the cost of motion blur.
+    ExposureFirst { max_exposure: Option<i64> },
+    /// Raise the gain first, then the exposure time. This keeps motion sharp, at the cost of noise.
+    GainFirst,
+}
+
+impl Default for ExposureStrategy {
+    fn default() -> Self {
+        ExposureStrategy::ExposureFirst { max_exposure: None }
+    }
+}
+
+impl Display for ExposureStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExposureStrategy::ExposureFirst {
+                max_exposure: Some(max_exposure),
+            } => write!(f, "Exposure first (up to {max_exposure})"),
+            ExposureStrategy::ExposureFirst { max_exposure: None } => {
+                write!(f, "Exposure first")
+            }
+            ExposureStrategy::GainFirst => write!(f, "Gain first"),
+        }
+    }
+}
+
+/// A difference between a saved control value and the camera's current one, as returned by `diff_controls()`.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ControlDiff {
+    control: KnownCameraControl,
+    saved: ControlValueSetter,
+    current: Option<ControlValueSetter>,
+}
+
+impl ControlDiff {
+    /// Creates a new [`ControlDiff`]. `current` is `None` if the control could not be read from the camera.
+    #[must_use]
+    pub fn new(
+        control: KnownCameraControl,
+        saved: ControlValueSetter,
+        current: Option<ControlValueSetter>,
+    ) -> Self {
+        ControlDiff {
+            control,
+            saved,
+            current,
+        }
+    }
+
+    /// Gets the [`KnownCameraControl`] that differs.
+    #[must_use]
+    pub fn control(&self) -> KnownCameraControl {
+        self.control
+    }
+
+    /// Gets the saved (profile) value.
+    #[must_use]
+    pub fn saved(&self) -> &ControlValueSetter {
+        &self.saved
+    }
+
+    /// Gets the camera's current value, or `None` if it could not be read.
+    #[must_use]
+    pub fn current(&self) -> Option<&ControlValueSetter> {
+        self.current.as_ref()
+    }
+
+    /// Gets how far the current value is from the saved one (`current - saved`), if both are numeric and of the same kind.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn delta(&self) -> Option<f64> {
+        match (&self.saved, self.current.as_ref()?) {
+            (ControlValueSetter::Integer(saved), ControlValueSetter::Integer(current))
+            | (ControlValueSetter::EnumValue(saved), ControlValueSetter::EnumValue(current)) => {
+                Some((current - saved) as f64)
+            }
+            (ControlValueSetter::Float(saved), ControlValueSetter::Float(current)) => {
+                Some(current - saved)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The saved settings of a camera: which camera they belong to, its [`CameraFormat`] and the values of its writable controls.
+/// With the `serialize` feature, `Camera::save_profile()` and `Camera::load_profile()` store and restore this as a JSON file.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct CameraProfile {
+    identifier: String,
+    format: CameraFormat,
+    controls: Vec<(KnownCameraControl, ControlValueSetter)>,
+}
+
+impl CameraProfile {
+    /// Creates a new [`CameraProfile`]. `identifier` should come from [`CameraProfile::identifier_for()`].
+    #[must_use]
+    pub fn new(
+        identifier: String,
+        format: CameraFormat,
+        controls: Vec<(KnownCameraControl, ControlValueSetter)>,
+    ) -> Self {
+        CameraProfile {
+            identifier,
+            format,
+            controls,
+        }
+    }
+
+    /// Gets the identifier a profile uses to match a camera: its name and misc info (e.g. the bus on V4L2), but not its index, which can change between sessions.
+    #[must_use]
+    pub fn identifier_for(info: &CameraInfo) -> String {
+        format!("{} ({})", info.human_name(), info.misc())
+    }
+
+    /// Gets the identifier of the camera this profile was saved from.
+    #[must_use]
+    pub fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    /// Returns `true` if this profile was saved from the camera described by `info`.
+    #[must_use]
+    pub fn matches(&self, info: &CameraInfo) -> bool {
+        self.identifier == CameraProfile::identifier_for(info)
+    }
+
+    /// Gets the saved [`CameraFormat`].
+    #[must_use]
+    pub fn format(&self) -> CameraFormat {
+        self.format
+    }
+
+    /// Gets the saved control values.
+    #[must_use]
+    pub fn controls(&self) -> &[(KnownCameraControl, ControlValueSetter)] {
+        &self.controls
+    }
+}
+
 /// The setter for a control value
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
@@ -1516,6 +3065,67 @@ pub fn mjpeg_to_rgb(_data: &[u8], _rgba: bool) -> Result<Vec<u8>, NokhwaError> {
     ))
 }
 
+/// Converts a MJPEG stream of `&[u8]` into a `Vec<u8>` of RGB888, decoded at `scale_num / 8` of its size (`scale_num` is clamped to 1 - 8).
+/// Decoding at a smaller scale skips most of the work of a full decode, which makes this much faster for e.g. thumbnails.
+///
+/// Returns the [`Resolution`] of the decoded image along with the pixels.
+/// # Errors
+/// If `mozjpeg` fails to read scanlines or setup the decompressor, this will error.
+#[cfg(all(feature = "mjpeg", not(target_arch = "wasm")))]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "mjpeg")))]
+pub fn mjpeg_to_rgb_scaled(
+    data: &[u8],
+    scale_num: u8,
+) -> Result<(Resolution, Vec<u8>), NokhwaError> {
+    use mozjpeg::Decompress;
+
+    let decode_error = |error: String| NokhwaError::ProcessFrameError {
+        src: FrameFormat::MJPEG,
+        destination: "RGB888 (scaled)".to_string(),
+        error,
+    };
+    let mut decompress = Decompress::new_mem(data).map_err(|why| decode_error(why.to_string()))?;
+    decompress.scale(scale_num.clamp(1, 8));
+    let mut jpeg_decompress = decompress
+        .rgb()
+        .map_err(|why| decode_error(why.to_string()))?;
+    #[allow(clippy::cast_possible_truncation)]
+    let resolution = Resolution::new(
+        jpeg_decompress.width() as u32,
+        jpeg_decompress.height() as u32,
+    );
+
+    let scanlines_res: Option<Vec<u8>> = jpeg_decompress.read_scanlines_flat();
+    if !jpeg_decompress.finish_decompress() {
+        return Err(decode_error(
+            "JPEG Decompressor did not finish.".to_string(),
+        ));
+    }
+    match scanlines_res {
+        Some(pixels) => Ok((resolution, pixels)),
+        None => Err(decode_error(
+            "Failed to get read readlines into RGB888 pixels!".to_string(),
+        )),
+    }
+}
+
+/// Converts a MJPEG stream of `&[u8]` into a `Vec<u8>` of RGB888, decoded at `scale_num / 8` of its size (`scale_num` is clamped to 1 - 8).
+/// Decoding at a smaller scale skips most of the work of a full decode, which makes this much faster for e.g. thumbnails.
+///
+/// Returns the [`Resolution`] of the decoded image along with the pixels.
+/// # Errors
+/// If `mozjpeg` fails to read scanlines or setup the decompressor, this will error.
+/// Without the `mjpeg` feature (or on WASM), this always errors with [`NotImplementedError`](NokhwaError::NotImplementedError).
+#[cfg(not(all(feature = "mjpeg", not(target_arch = "wasm"))))]
+pub fn mjpeg_to_rgb_scaled(
+    _data: &[u8],
+    _scale_num: u8,
+) -> Result<(Resolution, Vec<u8>), NokhwaError> {
+    Err(NokhwaError::NotImplementedError(
+        "Not available on WASM".to_string(),
+    ))
+}
+
 /// Equivalent to [`mjpeg_to_rgb`] except with a destination buffer.
 /// # Errors
 /// If the decoding fails (e.g. invalid MJPEG stream), the buffer is not large enough, or you are doing this on `WebAssembly`, this will error.
@@ -1820,3 +3430,128 @@ pub fn buf_yuv_420_to_rgb(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REQUESTED: CameraFormat = CameraFormat {
+        resolution: Resolution {
+            width_x: 1280,
+            height_y: 720,
+        },
+        format: FrameFormat::MJPEG,
+        frame_rate_numerator: 30,
+        frame_rate_denominator: 1,
+    };
+
+    fn nearest(candidates: &[CameraFormat]) -> Option<CameraFormat> {
+        RequestedFormat::with_formats(
+            RequestedFormatType::Nearest(REQUESTED),
+            &[FrameFormat::MJPEG, FrameFormat::YUYV],
+        )
+        .fulfill(candidates)
+    }
+
+    #[test]
+    fn resolution_mismatch_outweighs_frame_rate_mismatch() {
+        // the height is off by 4/3, less than the frame rate's 3/2, but resolution weighs more
+        let resolution_off = CameraFormat::new_from(1280, 960, FrameFormat::MJPEG, 30);
+        let frame_rate_off = CameraFormat::new_from(1280, 720, FrameFormat::MJPEG, 20);
+
+        let distance = |candidate: &CameraFormat| {
+            format_distance(
+                candidate,
+                &REQUESTED,
+                NEAREST_RESOLUTION_WEIGHT,
+                NEAREST_FRAME_RATE_WEIGHT,
+            )
+        };
+        assert!(distance(&frame_rate_off) < distance(&resolution_off));
+        assert!(format_distance(&REQUESTED, &REQUESTED, 2.0, 1.0).abs() < f64::EPSILON);
+
+        assert_eq!(
+            nearest(&[resolution_off, frame_rate_off]),
+            Some(frame_rate_off)
+        );
+    }
+
+    #[test]
+    fn nearest_prefers_requested_frame_format_on_ties() {
+        let yuyv = CameraFormat::new_from(640, 360, FrameFormat::YUYV, 30);
+        let mjpeg = CameraFormat::new_from(640, 360, FrameFormat::MJPEG, 30);
+
+        assert_eq!(nearest(&[yuyv, mjpeg]), Some(mjpeg));
+        assert_eq!(nearest(&[mjpeg, yuyv]), Some(mjpeg));
+    }
+
+    #[test]
+    fn nearest_resolves_without_the_requested_frame_format() {
+        let yuyv = CameraFormat::new_from(640, 360, FrameFormat::YUYV, 30);
+
+        assert_eq!(nearest(&[yuyv]), Some(yuyv));
+        assert_eq!(nearest(&[]), None);
+    }
+
+    #[test]
+    fn custom_frame_format_round_trips() {
+        for fourcc in [*b"Y16 ", *b"Z16 ", [b'Y', b'1', b'6', 0xA0], *b"a\\'\n"] {
+            let format = FrameFormat::Custom(fourcc);
+            assert_eq!(format.to_string().parse::<FrameFormat>().ok(), Some(format));
+        }
+        assert_eq!(
+            FrameFormat::Custom([b'Y', b'1', b'6', 0xA0]).to_string(),
+            "Y16\\xa0"
+        );
+        for fourcc in [
+            *b"NV12", *b"YUYV", *b"GRAY", *b"UYVY", *b"I420", *b"H264", *b"H265",
+        ] {
+            let format = FrameFormat::Custom(fourcc);
+            assert_eq!(
+                format.to_string(),
+                format!("FOURCC:{}", fourcc.escape_ascii())
+            );
+            assert_eq!(format.to_string().parse::<FrameFormat>().ok(), Some(format));
+            assert_ne!(fourcc.escape_ascii().to_string().parse().ok(), Some(format));
+        }
+        assert_eq!(FrameFormat::Custom(*b"Y16 ").to_string(), "Y16 ");
+        assert!("Y16".parse::<FrameFormat>().is_err());
+        assert!("Y16\\q".parse::<FrameFormat>().is_err());
+    }
+
+    #[test]
+    fn camera_format_keeps_trailing_space_of_fourcc() {
+        let format = CameraFormat::new_from(640, 480, FrameFormat::Custom(*b"Y16 "), 30);
+        assert_eq!(format.to_string(), "640x480@30/Y16 ");
+        assert_eq!(
+            " 640x480@30/Y16 ".parse::<CameraFormat>().ok(),
+            Some(format)
+        );
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn camera_format_deserializes_reduced() {
+        let from_json = |json: &str| serde_json::from_str::<CameraFormat>(json);
+        let resolution = r#""resolution":{"width_x":640,"height_y":480},"format":"MJPEG""#;
+
+        let whole = from_json(&format!(r#"{{{resolution},"frame_rate":30}}"#)).unwrap();
+        assert_eq!(
+            whole,
+            CameraFormat::new_from(640, 480, FrameFormat::MJPEG, 30)
+        );
+        assert_eq!(whole.frame_rate_ratio(), (30, 1));
+
+        let unreduced = from_json(&format!(
+            r#"{{{resolution},"frame_rate_numerator":60,"frame_rate_denominator":2}}"#
+        ))
+        .unwrap();
+        assert_eq!(unreduced, whole);
+        assert_eq!(unreduced.cmp(&whole), Ordering::Equal);
+
+        assert!(from_json(&format!(
+            r#"{{{resolution},"frame_rate_numerator":30,"frame_rate_denominator":0}}"#
+        ))
+        .is_err());
+    }
+}
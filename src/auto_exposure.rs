@@ -0,0 +1,133 @@
+/*
+ * Copyright 2022 l1npengtul <l1npengtul@protonmail.com> / The Nokhwa Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::Camera;
+use nokhwa_core::{buffer::Buffer, error::NokhwaError, pixel_format::LumaFormat};
+
+// Roughly the sRGB value of an 18% gray card.
+const DEFAULT_TARGET: u8 = 118;
+const DEFAULT_SPEED: f32 = 0.5;
+// Corrections smaller than this (in EV) are skipped, so the controls are not touched every frame once converged.
+const DEAD_BAND_EV: f32 = 0.05;
+
+/// A software auto-exposure loop, for cameras that only have manual exposure and gain controls.
+///
+/// Every frame captured through [`frame()`](SoftwareAutoExposure::frame) is measured by its mean luma, and the total exposure is moved towards the
+/// [`target()`](SoftwareAutoExposure::target) with [`Camera::set_ev()`], so the split between exposure and gain follows the camera's [`ExposureStrategy`](crate::utils::ExposureStrategy).
+/// Consider [`Camera::set_settle_frames()`], so frames still in flight with the previous setting are not measured again.
+pub struct SoftwareAutoExposure {
+    camera: Camera,
+    target: u8,
+    speed: f32,
+}
+
+impl SoftwareAutoExposure {
+    /// Creates a new [`SoftwareAutoExposure`] and opens the stream of the camera. The target mean luma defaults to `118` and the speed to `0.5`.
+    /// # Errors
+    /// If the camera has no integer range [`KnownCameraControl::Exposure`](crate::utils::KnownCameraControl::Exposure) control, this will error with
+    /// [`UnsupportedOperationError`](crate::NokhwaError::UnsupportedOperationError). If the stream fails to open, this will error.
+    pub fn new(mut camera: Camera) -> Result<Self, NokhwaError> {
+        if camera.current_ev().is_none() {
+            return Err(NokhwaError::UnsupportedOperationError(camera.backend()));
+        }
+        if !camera.is_stream_open() {
+            camera.open_stream()?;
+        }
+        Ok(SoftwareAutoExposure {
+            camera,
+            target: DEFAULT_TARGET,
+            speed: DEFAULT_SPEED,
+        })
+    }
+
+    /// Gets the target mean luma, from `0` to `255`.
+    #[must_use]
+    pub fn target(&self) -> u8 {
+        self.target
+    }
+
+    /// Sets the target mean luma, from `0` to `255`.
+    pub fn set_target(&mut self, target: u8) {
+        self.target = target;
+    }
+
+    /// Gets the fraction of the measured error corrected per frame.
+    #[must_use]
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Sets the fraction of the measured error (in EV) corrected per frame, clamped to `0.0..=1.0`.
+    /// `1.0` corrects in a single step but may oscillate on cameras that react slowly, `0.0` freezes the exposure.
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.clamp(0.0, 1.0);
+    }
+
+    /// Gets the camera.
+    #[must_use]
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    /// Gets the camera mutably, e.g. to change other controls.
+    pub fn camera_mut(&mut self) -> &mut Camera {
+        &mut self.camera
+    }
+
+    /// Gets the camera back out, leaving exposure and gain at their last values.
+    #[must_use]
+    pub fn into_camera(self) -> Camera {
+        self.camera
+    }
+
+    /// Captures a frame, then adjusts exposure and gain from its brightness for the next one.
+    /// # Errors
+    /// If the backend fails to get a frame, the frame cannot be decoded to luma, or setting a control fails, this will error.
+    pub fn frame(&mut self) -> Result<Buffer, NokhwaError> {
+        let frame = self.camera.frame()?;
+        let mean = mean_luma(&frame)?;
+        // A black frame has no measurable brightness, so treat it as one step above black.
+        let error_ev = (f32::from(self.target.max(1)) / mean.max(1.0)).log2();
+        let correction = error_ev * self.speed;
+        if correction.abs() >= DEAD_BAND_EV {
+            if let Some(current) = self.camera.current_ev() {
+                self.camera.set_ev(current + correction)?;
+            }
+        }
+        Ok(frame)
+    }
+}
+
+// Gets the mean luma of a frame, reading it directly where the format allows.
+#[allow(clippy::cast_precision_loss)]
+fn mean_luma(frame: &Buffer) -> Result<f32, NokhwaError> {
+    let (sum, count) = match frame.luma_iter() {
+        Some(luma) => luma.fold((0_u64, 0_u64), |(sum, count), value| {
+            (sum + u64::from(value), count + 1)
+        }),
+        None => frame
+            .decode_image::<LumaFormat>()?
+            .into_raw()
+            .into_iter()
+            .fold((0_u64, 0_u64), |(sum, count), value| {
+                (sum + u64::from(value), count + 1)
+            }),
+    };
+    if count == 0 {
+        return Ok(0.0);
+    }
+    Ok(sum as f32 / count as f32)
+}
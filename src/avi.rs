@@ -0,0 +1,151 @@
+/*
+ * Copyright 2022 l1npengtul <l1npengtul@protonmail.com> / The Nokhwa Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// A minimal RIFF AVI muxer for MJPEG frames: one video stream, a `movi` list, and an `idx1` index.
+
+const AVIF_HASINDEX: u32 = 0x10;
+const AVIIF_KEYFRAME: u32 = 0x10;
+
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+// Writes `fourcc` and a size placeholder, returning the placeholder's position for `end_chunk`.
+fn begin_chunk(out: &mut Vec<u8>, fourcc: &[u8; 4]) -> usize {
+    out.extend_from_slice(fourcc);
+    put_u32(out, 0);
+    out.len() - 4
+}
+
+// Fills in the size of the chunk started at `size_at` and pads it to an even length.
+#[allow(clippy::cast_possible_truncation)]
+fn end_chunk(out: &mut Vec<u8>, size_at: usize) {
+    let size = (out.len() - size_at - 4) as u32;
+    out[size_at..size_at + 4].copy_from_slice(&size.to_le_bytes());
+    if out.len() % 2 != 0 {
+        out.push(0);
+    }
+}
+
+fn begin_list(out: &mut Vec<u8>, list_type: &[u8; 4]) -> usize {
+    let size_at = begin_chunk(out, b"LIST");
+    out.extend_from_slice(list_type);
+    size_at
+}
+
+/// Muxes MJPEG `frames` of `width`x`height` into an AVI file played back at `numerator/denominator` frames per second.
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn mux_mjpeg_avi(
+    frames: &[&[u8]],
+    width: u32,
+    height: u32,
+    (numerator, denominator): (u32, u32),
+) -> Vec<u8> {
+    let (numerator, denominator) = (numerator.max(1), denominator.max(1));
+    let frame_count = frames.len() as u32;
+    let largest_frame = frames.iter().map(|frame| frame.len()).max().unwrap_or(0) as u32;
+
+    let mut out =
+        Vec::with_capacity(frames.iter().map(|frame| frame.len() + 24).sum::<usize>() + 512);
+    let riff_at = begin_chunk(&mut out, b"RIFF");
+    out.extend_from_slice(b"AVI ");
+
+    let hdrl_at = begin_list(&mut out, b"hdrl");
+    let avih_at = begin_chunk(&mut out, b"avih");
+    put_u32(
+        &mut out,
+        (1_000_000 * u64::from(denominator) / u64::from(numerator)) as u32,
+    );
+    put_u32(
+        &mut out,
+        (u64::from(largest_frame) * u64::from(numerator) / u64::from(denominator))
+            .min(u64::from(u32::MAX)) as u32,
+    );
+    put_u32(&mut out, 0);
+    put_u32(&mut out, AVIF_HASINDEX);
+    put_u32(&mut out, frame_count);
+    put_u32(&mut out, 0);
+    put_u32(&mut out, 1);
+    put_u32(&mut out, largest_frame);
+    put_u32(&mut out, width);
+    put_u32(&mut out, height);
+    out.extend_from_slice(&[0; 16]);
+    end_chunk(&mut out, avih_at);
+
+    let strl_at = begin_list(&mut out, b"strl");
+    let strh_at = begin_chunk(&mut out, b"strh");
+    out.extend_from_slice(b"vids");
+    out.extend_from_slice(b"MJPG");
+    put_u32(&mut out, 0);
+    put_u16(&mut out, 0);
+    put_u16(&mut out, 0);
+    put_u32(&mut out, 0);
+    // dwScale and dwRate, the frame rate is dwRate / dwScale
+    put_u32(&mut out, denominator);
+    put_u32(&mut out, numerator);
+    put_u32(&mut out, 0);
+    put_u32(&mut out, frame_count);
+    put_u32(&mut out, largest_frame);
+    put_u32(&mut out, u32::MAX);
+    put_u32(&mut out, 0);
+    put_u16(&mut out, 0);
+    put_u16(&mut out, 0);
+    put_u16(&mut out, width as u16);
+    put_u16(&mut out, height as u16);
+    end_chunk(&mut out, strh_at);
+
+    // BITMAPINFOHEADER
+    let strf_at = begin_chunk(&mut out, b"strf");
+    put_u32(&mut out, 40);
+    put_u32(&mut out, width);
+    put_u32(&mut out, height);
+    put_u16(&mut out, 1);
+    put_u16(&mut out, 24);
+    out.extend_from_slice(b"MJPG");
+    put_u32(&mut out, width.saturating_mul(height).saturating_mul(3));
+    out.extend_from_slice(&[0; 16]);
+    end_chunk(&mut out, strf_at);
+    end_chunk(&mut out, strl_at);
+    end_chunk(&mut out, hdrl_at);
+
+    let movi_at = begin_list(&mut out, b"movi");
+    // `idx1` offsets are relative to the `movi` list type
+    let movi_start = movi_at + 4;
+    let mut index = Vec::with_capacity(frames.len());
+    for frame in frames {
+        let frame_at = begin_chunk(&mut out, b"00dc");
+        out.extend_from_slice(frame);
+        index.push(((frame_at - 4 - movi_start) as u32, frame.len() as u32));
+        end_chunk(&mut out, frame_at);
+    }
+    end_chunk(&mut out, movi_at);
+
+    let idx1_at = begin_chunk(&mut out, b"idx1");
+    for (offset, size) in index {
+        out.extend_from_slice(b"00dc");
+        put_u32(&mut out, AVIIF_KEYFRAME);
+        put_u32(&mut out, offset);
+        put_u32(&mut out, size);
+    }
+    end_chunk(&mut out, idx1_at);
+
+    end_chunk(&mut out, riff_at);
+    out
+}
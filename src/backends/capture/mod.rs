@@ -17,9 +17,21 @@
 #[cfg(all(feature = "input-v4l", target_os = "linux"))]
 // I'm too lazy to set up a skeleton facade for V4L so here it will stay
 mod v4l2_backend;
+#[cfg(all(feature = "input-v4l", feature = "async", target_os = "linux"))]
+#[cfg_attr(
+    feature = "docs-features",
+    doc(cfg(all(feature = "input-v4l", feature = "async")))
+)]
+pub use v4l2_backend::V4LFrameStream;
+#[cfg(all(feature = "input-v4l", target_os = "linux"))]
+pub(crate) use v4l2_backend::{
+    associated_audio_device, device_in_use, device_node_path, V4L2_LOOPBACK_DRIVER,
+};
 #[cfg(all(feature = "input-v4l", target_os = "linux"))]
 #[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-v4l")))]
-pub use v4l2_backend::V4LCaptureDevice;
+pub use v4l2_backend::{
+    StreamMethod, V4LCaptureDevice, DEFAULT_BUFFER_COUNT, DEFAULT_MAX_STEPWISE_RESOLUTIONS,
+};
 #[cfg(any(
     all(feature = "input-msmf", target_os = "windows"),
     all(feature = "docs-only", feature = "docs-nolink", feature = "input-msmf")
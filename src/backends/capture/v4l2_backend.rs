@@ -14,30 +14,56 @@
  * limitations under the License.
  */
 
+#[cfg(feature = "async")]
+use futures_core::Stream;
+use image::{ImageBuffer, Rgb};
+use libc::{c_int, c_ulong, c_void};
 use nokhwa_core::types::RequestedFormatType;
 use nokhwa_core::{
     buffer::Buffer,
     error::NokhwaError,
-    traits::CaptureBackendTrait,
+    pixel_format::RgbFormat,
+    traits::{CameraControlResults, CaptureBackendTrait, PartialCameraControls},
     types::{
-        ApiBackend, CameraControl, CameraFormat, CameraIndex, CameraInfo, ControlValueDescription,
-        ControlValueSetter, FrameFormat, KnownCameraControl, KnownCameraControlFlag,
-        RequestedFormat, Resolution,
+        ApiBackend, CameraControl, CameraFormat, CameraIndex, CameraInfo, ColorMatrix,
+        ColorPrimaries, ColorRange, ColorTransfer, Colorimetry, ControlDependency,
+        ControlValueDescription, ControlValueSetter, FormatInfo, FormatNegotiation, FrameFormat,
+        FrameRateRange, KnownCameraControl, KnownCameraControlFlag, RequestedFormat, Resolution,
+        RollingShutter, UsbSpeed,
     },
 };
 use std::{
     borrow::Cow,
     collections::HashMap,
-    io::{self, ErrorKind},
+    fmt::{Display, Formatter},
+    fs, io, mem,
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
+    thread,
+    time::{Duration, Instant},
 };
+#[cfg(feature = "async")]
+use std::{
+    os::unix::io::RawFd,
+    pin::Pin,
+    task::{Context, Poll},
+};
+#[cfg(feature = "async")]
+use tokio::io::{unix::AsyncFd, Interest};
 use v4l::{
-    control::{Control, Flags, Type, Value},
+    buffer::{Flags as BufferFlags, Metadata},
+    capability::Flags as CapabilityFlags,
+    control::{Control, Description as ControlDescription, Flags, Type, Value},
+    format::{description::Flags as FormatFlags, Colorspace, Quantization, TransferFunction},
     frameinterval::FrameIntervalEnum,
-    framesize::FrameSizeEnum,
+    framesize::{FrameSizeEnum, Stepwise as FrameSizeStepwise},
     io::traits::CaptureStream,
-    prelude::MmapStream,
+    memory::Memory,
+    prelude::{MmapStream, UserptrStream},
+    v4l2::{self, vidioc},
+    v4l_sys::v4l2_requestbuffers,
     video::{capture::Parameters, Capture},
-    Device, Format, FourCC,
+    Device, Format, FourCC, Fraction,
 };
 
 /// Attempts to convert a [`KnownCameraControl`] into a V4L2 Control ID.
@@ -55,7 +81,7 @@ pub fn known_camera_control_to_id(ctrl: KnownCameraControl) -> u32 {
         KnownCameraControl::BacklightComp => 9_963_804,
         KnownCameraControl::Gain => 9_963_795,
         KnownCameraControl::Pan => 10_094_852,
-        KnownCameraControl::Tilt => 100_948_530,
+        KnownCameraControl::Tilt => 10_094_853,
         KnownCameraControl::Zoom => 10_094_862,
         KnownCameraControl::Exposure => 10_094_850,
         KnownCameraControl::Iris => 10_094_866,
@@ -79,7 +105,7 @@ pub fn id_to_known_camera_control(id: u32) -> KnownCameraControl {
         9_963_804 => KnownCameraControl::BacklightComp,
         9_963_795 => KnownCameraControl::Gain,
         10_094_852 => KnownCameraControl::Pan,
-        100_948_530 => KnownCameraControl::Tilt,
+        10_094_853 => KnownCameraControl::Tilt,
         10_094_862 => KnownCameraControl::Zoom,
         10_094_850 => KnownCameraControl::Exposure,
         10_094_866 => KnownCameraControl::Iris,
@@ -88,24 +114,211 @@ pub fn id_to_known_camera_control(id: u32) -> KnownCameraControl {
     }
 }
 
+// V4L2 control IDs that are not covered by `KnownCameraControl`, but are needed to describe control dependencies.
+const V4L2_CID_AUTO_WHITE_BALANCE: u32 = 9_963_788;
+const V4L2_CID_RED_BALANCE: u32 = 9_963_790;
+const V4L2_CID_BLUE_BALANCE: u32 = 9_963_791;
+const V4L2_CID_AUTOGAIN: u32 = 9_963_794;
+const V4L2_CID_EXPOSURE_AUTO: u32 = 10_094_849;
+const V4L2_CID_EXPOSURE_AUTO_PRIORITY: u32 = 10_094_851;
+const V4L2_CID_FOCUS_AUTO: u32 = 10_094_860;
+// V4L2 control IDs whose units are defined by the spec, see `control_unit`.
+const V4L2_CID_WHITE_BALANCE_TEMPERATURE: u32 = 9_963_802;
+const V4L2_CID_EXPOSURE_ABSOLUTE: u32 = 10_094_850;
+const V4L2_CID_PAN_RELATIVE: u32 = 10_094_852;
+const V4L2_CID_TILT_RELATIVE: u32 = 10_094_853;
+const V4L2_CID_PAN_ABSOLUTE: u32 = 10_094_856;
+const V4L2_CID_TILT_ABSOLUTE: u32 = 10_094_857;
+const V4L2_CID_FOCUS_ABSOLUTE: u32 = 10_094_858;
+const V4L2_CID_FOCUS_RELATIVE: u32 = 10_094_859;
+const V4L2_CID_ZOOM_ABSOLUTE: u32 = 10_094_861;
+const V4L2_CID_ZOOM_RELATIVE: u32 = 10_094_862;
+const V4L2_CID_IRIS_ABSOLUTE: u32 = 10_094_865;
+const V4L2_CID_IRIS_RELATIVE: u32 = 10_094_866;
+// Image source/process class controls that sensor drivers expose, see `rolling_shutter_info`.
+const V4L2_CID_HBLANK: u32 = 10_356_994;
+const V4L2_CID_PIXEL_RATE: u32 = 10_422_530;
+const V4L2_CID_CAMERA_SENSOR_ROTATION: u32 = 10_094_883;
+// `V4L2_EXPOSURE_MANUAL` from `enum v4l2_exposure_auto_type`
+const V4L2_EXPOSURE_MANUAL: i64 = 1;
+// `V4L2_CTRL_ID2CLASS`
+const V4L2_CTRL_CLASS_MASK: u32 = 0x0FFF_0000;
+// Drivers that return `EBUSY` for `VIDIOC_S_PARM` while streaming, so even frame rate changes need a new stream.
+const DRIVERS_REJECTING_STREAMING_S_PARM: &[&str] = &["uvcvideo"];
+// `V4L2_PIX_FMT_Y16_BE`: "Y16 " with the big endian bit (1 << 31) set
+const V4L2_PIX_FMT_Y16_BE: [u8; 4] = [b'Y', b'1', b'6', b' ' | 0x80];
+// How long `hard_reset()` waits for the USB device to come back with a video node.
+const HARD_RESET_TIMEOUT: Duration = Duration::from_secs(10);
+const HARD_RESET_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// the driver name `v4l2loopback` reports in `VIDIOC_QUERYCAP`
+pub(crate) const V4L2_LOOPBACK_DRIVER: &str = "v4l2 loopback";
+
+/// The default cap on the number of [`Resolution`]s generated for each stepwise frame size range. See [`V4LCaptureDevice::new_with_stepwise_limit()`].
+pub const DEFAULT_MAX_STEPWISE_RESOLUTIONS: usize = 256;
+
+/// The default number of frame buffers queued to the driver while streaming, the same as `v4l` uses. See [`V4LCaptureDevice::set_buffer_count()`].
+pub const DEFAULT_BUFFER_COUNT: u32 = 4;
+// How many buffers `max_stream_buffers()` asks for, V4L2's `VIDEO_MAX_FRAME`.
+const MAX_BUFFER_COUNT_PROBE: u32 = 32;
+// The buffer count used for `RequestedFormatType::LowestLatency`: one being filled by the driver, one being read.
+const LOW_LATENCY_BUFFER_COUNT: u32 = 2;
+// `MPOL_DEFAULT` and `MPOL_PREFERRED` from `linux/mempolicy.h`
+const MPOL_DEFAULT: c_int = 0;
+const MPOL_PREFERRED: c_int = 1;
+// The node mask size used with `set_mempolicy`/`get_mempolicy`, the kernel's largest `MAX_NUMNODES`.
+const NODE_MASK_BITS: usize = 1024;
+const NODE_MASK_WORDS: usize = NODE_MASK_BITS / c_ulong::BITS as usize;
+// `POLLIN` from `poll.h`
+#[cfg(feature = "async")]
+const POLLIN: i16 = 0x001;
+
+// Enumerated formats by device, `None` while the cache is disabled. See [`V4LCaptureDevice::set_format_cache_enabled()`].
+static FORMAT_CACHE: Mutex<Option<HashMap<FormatCacheKey, Vec<CameraFormat>>>> = Mutex::new(None);
+
+// The card name and bus are part of the key, so a different camera showing up under the same index is enumerated again.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct FormatCacheKey {
+    index: u32,
+    bus: String,
+    card: String,
+    use_libv4l: bool,
+    max_stepwise_resolutions: usize,
+}
+
+/// How [`V4LCaptureDevice`] exchanges frame buffers with the driver. See [`V4LCaptureDevice::set_stream_method()`].
+/// - `Mmap`: The driver allocates the buffers and they are memory mapped (`V4L2_MEMORY_MMAP`). Supported by almost every driver.
+/// - `UserPtr`: The buffers are allocated in user space and handed to the driver (`V4L2_MEMORY_USERPTR`). Some embedded drivers only support this, and on others it has a lower latency.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-v4l")))]
+pub enum StreamMethod {
+    #[default]
+    Mmap,
+    UserPtr,
+}
+
+impl Display for StreamMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+// `MmapStream` and `UserptrStream` are distinct types, so the open stream is one of these.
+enum StreamHandle<'a> {
+    Mmap(MmapStream<'a>),
+    UserPtr(UserptrStream),
+}
+
+impl StreamHandle<'_> {
+    fn set_timeout(&mut self, timeout: Option<Duration>) {
+        match (self, timeout) {
+            (StreamHandle::Mmap(stream), Some(timeout)) => stream.set_timeout(timeout),
+            (StreamHandle::Mmap(stream), None) => stream.clear_timeout(),
+            (StreamHandle::UserPtr(stream), Some(timeout)) => stream.set_timeout(timeout),
+            (StreamHandle::UserPtr(stream), None) => stream.clear_timeout(),
+        }
+    }
+
+    fn next(&mut self) -> io::Result<(&[u8], &Metadata)> {
+        match self {
+            StreamHandle::Mmap(stream) => stream.next(),
+            StreamHandle::UserPtr(stream) => stream.next(),
+        }
+    }
+
+    // Starts a freshly opened stream without waiting for a frame, unlike the first `next()`.
+    // Every buffer but the first is queued: `next()` queues the buffer it handed out last (initially the first) before dequeueing.
+    #[cfg(feature = "async")]
+    fn start_without_dequeue(&mut self, buffer_count: u32) -> io::Result<()> {
+        for index in 1..buffer_count as usize {
+            match self {
+                StreamHandle::Mmap(stream) => CaptureStream::queue(stream, index)?,
+                StreamHandle::UserPtr(stream) => CaptureStream::queue(stream, index)?,
+            }
+        }
+        match self {
+            StreamHandle::Mmap(stream) => v4l::io::traits::Stream::start(stream),
+            StreamHandle::UserPtr(stream) => v4l::io::traits::Stream::start(stream),
+        }
+    }
+}
+
 /// The backend struct that interfaces with V4L2.
 /// To see what this does, please see [`CaptureBackendTrait`].
 /// # Quirks
 /// - Calling [`set_resolution()`](CaptureBackendTrait::set_resolution), [`set_frame_rate()`](CaptureBackendTrait::set_frame_rate), or [`set_frame_format()`](CaptureBackendTrait::set_frame_format) each internally calls [`set_camera_format()`](CaptureBackendTrait::set_camera_format).
+/// - Formats emulated by libv4l are only offered when allowed, see [`new_with_libv4l()`](Self::new_with_libv4l).
+/// - Calling [`set_camera_format()`](CaptureBackendTrait::set_camera_format) while streaming with only a new frame rate keeps the stream running, unless the driver (e.g. `uvcvideo`) rejects `VIDIOC_S_PARM` while streaming.
+/// See [`format_change_disruptive()`](CaptureBackendTrait::format_change_disruptive).
+/// - Any other call to [`set_camera_format()`](CaptureBackendTrait::set_camera_format) while streaming releases the old buffers right before the new format is set and allocates the new ones right after. V4L2 does not allow the new buffers to be queued before the old ones are released, so a short gap in frames is unavoidable. If the new format fails, the old one is restored and the stream reopened.
+/// - Format enumeration is redone every time a device is opened, unless the format cache is enabled. See [`set_format_cache_enabled()`](Self::set_format_cache_enabled).
+/// - Frame buffers are memory mapped by default. If the driver cannot allocate them, [`open_stream()`](CaptureBackendTrait::open_stream) falls back to user pointer buffers. See [`set_stream_method()`](Self::set_stream_method).
+/// - The hint from [`set_buffer_size_hint()`](CaptureBackendTrait::set_buffer_size_hint) is passed as `sizeimage`, which V4L2 drivers only take into account for compressed formats such as MJPEG.
+/// - Frame buffers can be placed on a NUMA node with [`set_numa_node()`](Self::set_numa_node). This only works for drivers that allocate them from regular kernel memory (e.g. `uvcvideo`), not from reserved DMA memory, and not for user pointer buffers.
+/// - [`hard_reset()`](CaptureBackendTrait::hard_reset) re-enumerates the USB device through its sysfs `authorized` attribute, which is only writable by root by default. Cameras not on USB cannot be reset.
 #[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-v4l")))]
 pub struct V4LCaptureDevice<'a> {
     camera_format: CameraFormat,
     camera_info: CameraInfo,
     device: Device,
-    stream_handle: Option<MmapStream<'a>>,
+    stream_handle: Option<StreamHandle<'a>>,
+    stream_method: StreamMethod,
+    buffer_count: u32,
+    numa_node: Option<u32>,
+    frame_rate_settable: bool,
+    use_libv4l: bool,
+    max_stepwise_resolutions: usize,
+    colorimetry: Option<Colorimetry>,
+    last_frame_timestamp: Option<Duration>,
+    last_frame_keyframe: Option<bool>,
+    last_frame_sequence: Option<u32>,
+    last_frame_bytes: Option<usize>,
+    negotiation: Option<FormatNegotiation>,
+    buffer_size_hint: Option<u32>,
 }
 
 impl<'a> V4LCaptureDevice<'a> {
     /// Creates a new capture device using the `V4L2` backend. Indexes are gives to devices by the OS, and usually numbered by order of discovery.
+    ///
+    /// This allows formats emulated by libv4l. See [`new_with_libv4l()`](Self::new_with_libv4l).
     /// # Errors
     /// This function will error if the camera is currently busy or if `V4L2` can't read device information.
-    #[allow(clippy::too_many_lines)]
     pub fn new(index: &CameraIndex, cam_fmt: RequestedFormat) -> Result<Self, NokhwaError> {
+        Self::new_with_libv4l(index, cam_fmt, true)
+    }
+
+    /// Creates a new capture device using the `V4L2` backend, choosing whether formats emulated by libv4l may be used.
+    ///
+    /// When `v4l` is built with its `libv4l` feature, the device is opened through libv4l, which adds emulated formats
+    /// (e.g. RGB converted in software from the camera's native YUYV or MJPEG) to the format list.
+    /// - `use_libv4l = true`: Emulated formats are listed and may be negotiated. They cost CPU time and are not bit-exact to what the sensor delivered.
+    /// - `use_libv4l = false`: Only formats native to the device are listed and negotiated, so frames are passed through unconverted.
+    ///
+    /// Without the `libv4l` feature, the raw device node is used and every format is native, so both modes behave the same.
+    /// [`detailed_formats()`](CaptureBackendTrait::detailed_formats) always lists every format, with emulated ones flagged.
+    /// # Errors
+    /// This function will error if the camera is currently busy or if `V4L2` can't read device information.
+    pub fn new_with_libv4l(
+        index: &CameraIndex,
+        cam_fmt: RequestedFormat,
+        use_libv4l: bool,
+    ) -> Result<Self, NokhwaError> {
+        Self::new_with_stepwise_limit(index, cam_fmt, use_libv4l, DEFAULT_MAX_STEPWISE_RESOLUTIONS)
+    }
+
+    /// Same as [`new_with_libv4l()`](Self::new_with_libv4l), but with a custom cap on how many [`Resolution`]s are generated for each stepwise frame size range.
+    ///
+    /// Cameras that report a stepwise range (e.g. 2x2 to 4096x4096 in steps of 1) are sampled evenly from the smallest to the largest size,
+    /// taking at most `max_stepwise_resolutions` sizes, so enumeration stays bounded regardless of the step granularity. The default is [`DEFAULT_MAX_STEPWISE_RESOLUTIONS`].
+    /// # Errors
+    /// This function will error if the camera is currently busy or if `V4L2` can't read device information.
+    #[allow(clippy::too_many_lines)]
+    pub fn new_with_stepwise_limit(
+        index: &CameraIndex,
+        cam_fmt: RequestedFormat,
+        use_libv4l: bool,
+        max_stepwise_resolutions: usize,
+    ) -> Result<Self, NokhwaError> {
         let index = index.clone();
         let device = match Device::new(index.as_index()? as usize) {
             Ok(dev) => dev,
@@ -117,86 +330,42 @@ impl<'a> V4LCaptureDevice<'a> {
             }
         };
 
-        // get all formats
-        // get all fcc
-        let mut camera_formats = vec![];
+        let device_caps = device
+            .query_caps()
+            .map_err(|why| NokhwaError::GetPropertyError {
+                property: "Device Capabilities".to_string(),
+                error: why.to_string(),
+            })?;
+        // e.g. the metadata node UVC cameras expose next to their capture node, which has no formats to fulfill
+        if !device_caps
+            .capabilities
+            .contains(CapabilityFlags::VIDEO_CAPTURE)
+        {
+            return Err(NokhwaError::OpenDeviceError(
+                index.to_string(),
+                format!(
+                    "Not a video capture device (capabilities: {}). If this is a metadata node, open the camera's capture node instead",
+                    device_caps.capabilities
+                ),
+            ));
+        }
 
-        let frame_formats = match device.enum_formats() {
-            Ok(formats) => {
-                let mut frame_format_vec = vec![];
+        let cache_key = FormatCacheKey {
+            index: index.as_index()?,
+            bus: device_caps.bus.clone(),
+            card: device_caps.card.clone(),
+            use_libv4l,
+            max_stepwise_resolutions,
+        };
+        let camera_formats = match cached_camera_formats(&cache_key) {
+            Some(formats) => formats,
+            None => {
+                let formats =
+                    enumerate_camera_formats(&device, use_libv4l, max_stepwise_resolutions)?;
+                cache_camera_formats(cache_key, &formats);
                 formats
-                    .iter()
-                    .for_each(|fmt| frame_format_vec.push(fmt.fourcc));
-                frame_format_vec.dedup();
-                Ok(frame_format_vec)
             }
-            Err(why) => Err(NokhwaError::GetPropertyError {
-                property: "FrameFormat".to_string(),
-                error: why.to_string(),
-            }),
-        }?;
-
-        for ff in frame_formats {
-            let framefmt = match fourcc_to_frameformat(ff) {
-                Some(s) => s,
-                None => continue,
-            };
-            // i write unmaintainable blobs of code because i am so cute uwu~~
-            let mut formats = device
-                .enum_framesizes(ff)
-                .map_err(|why| NokhwaError::GetPropertyError {
-                    property: "ResolutionList".to_string(),
-                    error: why.to_string(),
-                })?
-                .into_iter()
-                .flat_map(|x| {
-                    match x.size {
-                        FrameSizeEnum::Discrete(d) => [Resolution::new(d.width, d.height)].to_vec(),
-                        // we step over each step, getting a new resolution.
-                        FrameSizeEnum::Stepwise(s) => (s.min_width..s.max_width)
-                            .step_by(s.step_width as usize)
-                            .zip((s.min_height..s.max_height).step_by(s.step_height as usize))
-                            .map(|(x, y)| Resolution::new(x, y))
-                            .collect(),
-                    }
-                })
-                .flat_map(|res| {
-                    device
-                        .enum_frameintervals(ff, res.x(), res.y())
-                        .unwrap_or_default()
-                        .into_iter()
-                        .flat_map(|x| match x.interval {
-                            FrameIntervalEnum::Discrete(dis) => {
-                                if dis.numerator == 1 {
-                                    vec![CameraFormat::new(
-                                        Resolution::new(x.width, x.height),
-                                        framefmt,
-                                        dis.denominator,
-                                    )]
-                                } else {
-                                    vec![]
-                                }
-                            }
-                            FrameIntervalEnum::Stepwise(step) => {
-                                let mut intvec = vec![];
-                                for fstep in (step.min.numerator..step.max.numerator)
-                                    .step_by(step.step.numerator as usize)
-                                {
-                                    if step.max.denominator != 1 || step.min.denominator != 1 {
-                                        intvec.push(CameraFormat::new(
-                                            Resolution::new(x.width, x.height),
-                                            framefmt,
-                                            fstep,
-                                        ));
-                                    }
-                                }
-                                intvec
-                            }
-                        })
-                })
-                .collect::<Vec<CameraFormat>>();
-            camera_formats.append(&mut formats);
-        }
+        };
 
         let format = cam_fmt
             .fulfill(&camera_formats)
@@ -216,35 +385,56 @@ impl<'a> V4LCaptureDevice<'a> {
                 error: why.to_string(),
             });
         }
-        if let Err(why) = device.set_params(&Parameters::with_fps(format.frame_rate())) {
-            return Err(NokhwaError::SetPropertyError {
-                property: "Frame rate".to_string(),
-                value: format.frame_rate().to_string(),
-                error: why.to_string(),
-            });
-        }
+        let frame_rate_settable = match device.set_params(&frame_rate_to_parameters(format)) {
+            Ok(_) => true,
+            Err(why) if is_unsupported_ioctl(&why) => false,
+            Err(why) => {
+                return Err(NokhwaError::SetPropertyError {
+                    property: "Frame rate".to_string(),
+                    value: format.frame_rate().to_string(),
+                    error: why.to_string(),
+                });
+            }
+        };
 
-        let device_caps = device
-            .query_caps()
-            .map_err(|why| NokhwaError::GetPropertyError {
-                property: "Device Capabilities".to_string(),
-                error: why.to_string(),
-            })?;
+        let mut camera_info = CameraInfo::new(
+            &device_caps.card,
+            &device_caps.driver,
+            &format!("{} {:?}", device_caps.bus, device_caps.version),
+            index,
+        );
+        camera_info.set_is_virtual(device_caps.driver == V4L2_LOOPBACK_DRIVER);
+        camera_info.set_device_path(
+            &device_node_path(&PathBuf::from(format!("/dev/video{}", index.as_index()?)))
+                .to_string_lossy(),
+        );
+        if let Some(audio_device) = associated_audio_device(index.as_index()?) {
+            camera_info.set_associated_audio_device(&audio_device);
+        }
 
         let mut v4l2 = V4LCaptureDevice {
             camera_format: format,
-            camera_info: CameraInfo::new(
-                &device_caps.card,
-                &device_caps.driver,
-                &format!("{} {:?}", device_caps.bus, device_caps.version),
-                index,
-            ),
+            camera_info,
             device,
             stream_handle: None,
+            stream_method: StreamMethod::default(),
+            buffer_count: DEFAULT_BUFFER_COUNT,
+            numa_node: None,
+            frame_rate_settable,
+            use_libv4l,
+            max_stepwise_resolutions,
+            colorimetry: None,
+            last_frame_timestamp: None,
+            last_frame_keyframe: None,
+            last_frame_sequence: None,
+            last_frame_bytes: None,
+            negotiation: None,
+            buffer_size_hint: None,
         };
 
         v4l2.force_refresh_camera_format()?;
-        if v4l2.camera_format() != format {
+        v4l2.negotiation = Some(FormatNegotiation::new(format, v4l2.camera_format));
+        if !cam_fmt.lenient() && !v4l2.matches_camera_format(format) {
             return Err(NokhwaError::SetPropertyError {
                 property: "CameraFormat".to_string(),
                 value: String::new(),
@@ -252,6 +442,13 @@ impl<'a> V4LCaptureDevice<'a> {
             });
         }
 
+        if let Some(range) = cam_fmt.frame_rate_range() {
+            v4l2.set_frame_rate_range(range)?;
+        }
+        if cam_fmt.requested_format_type() == RequestedFormatType::LowestLatency {
+            v4l2.buffer_count = LOW_LATENCY_BUFFER_COUNT;
+        }
+
         Ok(v4l2)
     }
 
@@ -290,9 +487,10 @@ impl<'a> V4LCaptureDevice<'a> {
                             resolutions.push(Resolution::new(dis.width, dis.height));
                         }
                         FrameSizeEnum::Stepwise(step) => {
-                            resolutions.push(Resolution::new(step.min_width, step.min_height));
-                            resolutions.push(Resolution::new(step.max_width, step.max_height));
-                            // TODO: Respect step size
+                            resolutions.append(&mut sample_stepwise_resolutions(
+                                &step,
+                                self.max_stepwise_resolutions,
+                            ));
                         }
                     }
                 }
@@ -312,42 +510,340 @@ impl<'a> V4LCaptureDevice<'a> {
         &self.device
     }
 
+    /// Enables or disables the format cache, which is disabled by default.
+    ///
+    /// While enabled, the formats enumerated when a device is opened are kept per device (index, card name and bus), and opening the same device again
+    /// skips the enumeration. This speeds up apps that open and close the same camera repeatedly. Disabling the cache drops everything cached so far.
+    ///
+    /// A cached list is not refreshed by itself, e.g. after a firmware update changes the formats on offer. Use [`clear_format_cache()`](Self::clear_format_cache) for that.
+    pub fn set_format_cache_enabled(enabled: bool) {
+        let mut cache = FORMAT_CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+        match (enabled, cache.is_some()) {
+            (true, false) => *cache = Some(HashMap::new()),
+            (false, true) => *cache = None,
+            _ => {}
+        }
+    }
+
+    /// Returns `true` if the format cache is enabled. See [`set_format_cache_enabled()`](Self::set_format_cache_enabled).
+    #[must_use]
+    pub fn format_cache_enabled() -> bool {
+        FORMAT_CACHE
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_some()
+    }
+
+    /// Drops every cached format list, so the next open of each device enumerates its formats again. The cache stays enabled.
+    pub fn clear_format_cache() {
+        if let Some(cache) = FORMAT_CACHE
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_mut()
+        {
+            cache.clear();
+        }
+    }
+
+    /// Enables the format cache and fills it for the device at `index`, without opening a stream or changing its format.
+    /// A later [`new()`](Self::new) with the same `index` then skips the enumeration.
+    ///
+    /// The formats are enumerated as [`new()`](Self::new) does, so opening through [`new_with_libv4l()`](Self::new_with_libv4l) or
+    /// [`new_with_stepwise_limit()`](Self::new_with_stepwise_limit) with other settings still enumerates once.
+    /// # Errors
+    /// This function will error if the device cannot be opened or `V4L2` can't read device information.
+    pub fn prewarm_format_cache(index: &CameraIndex) -> Result<(), NokhwaError> {
+        Self::set_format_cache_enabled(true);
+        let device = Device::new(index.as_index()? as usize).map_err(|why| {
+            NokhwaError::OpenDeviceError(index.to_string(), format!("V4L2 Error: {}", why))
+        })?;
+        let device_caps = device
+            .query_caps()
+            .map_err(|why| NokhwaError::GetPropertyError {
+                property: "Device Capabilities".to_string(),
+                error: why.to_string(),
+            })?;
+        let formats = enumerate_camera_formats(&device, true, DEFAULT_MAX_STEPWISE_RESOLUTIONS)?;
+        cache_camera_formats(
+            FormatCacheKey {
+                index: index.as_index()?,
+                bus: device_caps.bus,
+                card: device_caps.card,
+                use_libv4l: true,
+                max_stepwise_resolutions: DEFAULT_MAX_STEPWISE_RESOLUTIONS,
+            },
+            &formats,
+        );
+        Ok(())
+    }
+
     /// Get the inner device (mutable) for e.g. Controls
     pub fn inner_device_mut(&mut self) -> &mut Device {
         &mut self.device
     }
 
+    /// Captures a frame and decodes it to RGB888, whatever the [`FrameFormat`] of the current [`CameraFormat`] is (e.g. MJPEG, YUYV or NV12).
+    /// This is the same as decoding the [`Buffer`] from [`frame()`](CaptureBackendTrait::frame) with [`RgbFormat`].
+    /// # Errors
+    /// If the frame cannot be captured or decoded (e.g. MJPEG without the `mjpeg` feature), this will error.
+    pub fn frame_decoded(&mut self) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, NokhwaError> {
+        self.frame()?.decode_image::<RgbFormat>()
+    }
+
+    /// Gets the path of the device node this camera was opened from, e.g. `/dev/video0`, with symlinks resolved.
+    /// Use this to open the same device in other tools (e.g. ffmpeg or GStreamer). It is also available as [`CameraInfo::device_path()`].
+    #[must_use]
+    pub fn device_path(&self) -> PathBuf {
+        self.camera_info
+            .device_path()
+            .map_or_else(PathBuf::new, PathBuf::from)
+    }
+
+    /// Gets the [`StreamMethod`] used when the stream is opened.
+    #[must_use]
+    pub fn stream_method(&self) -> StreamMethod {
+        self.stream_method
+    }
+
+    /// Sets the [`StreamMethod`] used when the stream is opened. The default is [`StreamMethod::Mmap`].
+    ///
+    /// This takes effect the next time the stream is opened, an open stream keeps its buffers until then.
+    pub fn set_stream_method(&mut self, stream_method: StreamMethod) {
+        self.stream_method = stream_method;
+    }
+
+    /// Gets the number of frame buffers queued to the driver while streaming.
+    #[must_use]
+    pub fn buffer_count(&self) -> u32 {
+        self.buffer_count
+    }
+
+    /// Sets the number of frame buffers queued to the driver while streaming. The default is [`DEFAULT_BUFFER_COUNT`].
+    ///
+    /// More buffers let the driver keep capturing while the app is busy, avoiding dropped frames at high frame rates. Fewer buffers use less memory.
+    /// The driver may adjust the count to what it supports.
+    ///
+    /// The buffers are allocated when the stream is opened, so an open stream has to be reopened (e.g. with [`stop_stream()`](CaptureBackendTrait::stop_stream)
+    /// and [`open_stream()`](CaptureBackendTrait::open_stream)) for the new count to take effect. Streams reopened by [`set_camera_format()`](CaptureBackendTrait::set_camera_format) use it.
+    /// # Errors
+    /// If `count` is 0, this will error.
+    pub fn set_buffer_count(&mut self, count: u32) -> Result<(), NokhwaError> {
+        if count == 0 {
+            return Err(NokhwaError::SetPropertyError {
+                property: "Buffer Count".to_string(),
+                value: count.to_string(),
+                error: "At least one buffer is needed".to_string(),
+            });
+        }
+        self.buffer_count = count;
+        Ok(())
+    }
+
+    /// Gets the NUMA node the stream's frame buffers are placed on, or `None` if the kernel decides.
+    #[must_use]
+    pub fn numa_node(&self) -> Option<u32> {
+        self.numa_node
+    }
+
+    /// Sets the NUMA node to place the stream's frame buffers on, e.g. the node of the socket that processes the frames on a multi-socket capture server, so they are read from local memory.
+    /// `None` (the default) leaves it to the kernel, which usually picks the node of the CPU that opens the stream.
+    ///
+    /// The node is preferred, not required: if it has no free memory, the buffers are placed elsewhere. See the quirks of [`V4LCaptureDevice`] for the drivers this works with.
+    /// This takes effect the next time the stream is opened, like [`set_buffer_count()`](Self::set_buffer_count).
+    /// # Errors
+    /// If the node does not exist, this will error.
+    pub fn set_numa_node(&mut self, node: Option<u32>) -> Result<(), NokhwaError> {
+        if let Some(node) = node {
+            if node as usize >= NODE_MASK_BITS
+                || !Path::new(&format!("/sys/devices/system/node/node{node}")).is_dir()
+            {
+                return Err(NokhwaError::SetPropertyError {
+                    property: "NUMA Node".to_string(),
+                    value: node.to_string(),
+                    error: "No such node".to_string(),
+                });
+            }
+        }
+        self.numa_node = node;
+        Ok(())
+    }
+
+    /// Gets the largest number of frame buffers the driver grants at the current format, to size [`set_buffer_count()`](Self::set_buffer_count) to the hardware.
+    ///
+    /// This probes with `VIDIOC_REQBUFS`: it asks for 32 memory mapped buffers (V4L2's `VIDEO_MAX_FRAME`), reads back how many the driver granted, then releases them again.
+    /// # Errors
+    /// If the stream is open (its buffers are still allocated), or the driver rejects the request, this will error.
+    pub fn max_stream_buffers(&self) -> Result<u32, NokhwaError> {
+        let probe_error = |error: String| NokhwaError::GetPropertyError {
+            property: "Max Stream Buffers".to_string(),
+            error,
+        };
+        if self.stream_handle.is_some() {
+            return Err(probe_error(
+                "The stream has to be stopped first".to_string(),
+            ));
+        }
+
+        let fd = self.device.handle().fd();
+        let request_buffers = |count: u32| {
+            let mut request = v4l2_requestbuffers {
+                count,
+                type_: v4l::buffer::Type::VideoCapture as u32,
+                memory: Memory::Mmap as u32,
+                ..unsafe { mem::zeroed() }
+            };
+            unsafe {
+                v4l2::ioctl(
+                    fd,
+                    vidioc::VIDIOC_REQBUFS,
+                    std::ptr::addr_of_mut!(request).cast(),
+                )
+            }
+            .map(|()| request.count)
+        };
+        let granted =
+            request_buffers(MAX_BUFFER_COUNT_PROBE).map_err(|why| probe_error(why.to_string()))?;
+        // release them, so the stream can allocate its own
+        request_buffers(0).map_err(|why| probe_error(why.to_string()))?;
+        Ok(granted)
+    }
+
+    /// Gets the value of the V4L2 control with the raw ID `id`, without going through [`KnownCameraControl`], e.g. for UVC extension unit (vendor) controls.
+    ///
+    /// Strings and compound values cannot be read, as `v4l` only reads integer, menu and boolean controls.
+    /// # Errors
+    /// If the control does not exist or its value cannot be read, this will error.
+    pub fn raw_control(&self, id: u32) -> Result<ControlValueSetter, NokhwaError> {
+        let control = self
+            .device
+            .control(id)
+            .map_err(|why| NokhwaError::GetPropertyError {
+                property: raw_control_name(id),
+                error: why.to_string(),
+            })?;
+        match control.value {
+            Value::None => Ok(ControlValueSetter::None),
+            Value::Integer(value) => Ok(ControlValueSetter::Integer(value)),
+            Value::Boolean(value) => Ok(ControlValueSetter::Boolean(value)),
+            Value::String(value) => Ok(ControlValueSetter::String(value)),
+            Value::CompoundU8(bytes) | Value::CompoundPtr(bytes) => {
+                Ok(ControlValueSetter::Bytes(bytes))
+            }
+            _ => Err(NokhwaError::GetPropertyError {
+                property: raw_control_name(id),
+                error: "Unsupported control type".to_string(),
+            }),
+        }
+    }
+
+    /// Sets the V4L2 control with the raw ID `id` to `value`, without going through [`KnownCameraControl`], e.g. for UVC extension unit (vendor) controls.
+    ///
+    /// The value is checked against the control's type (and menu items), but is not read back to verify it.
+    /// # Errors
+    /// If the control does not exist, `value` does not fit its type, or the driver rejects it, this will error.
+    pub fn set_raw_control(
+        &mut self,
+        id: u32,
+        value: ControlValueSetter,
+    ) -> Result<(), NokhwaError> {
+        let property = raw_control_name(id);
+        let descriptor = self
+            .device
+            .query_controls()
+            .map_err(|why| NokhwaError::GetPropertyError {
+                property: "V4L2 Controls".to_string(),
+                error: why.to_string(),
+            })?
+            .into_iter()
+            .find(|desc| desc.id == id)
+            .ok_or_else(|| NokhwaError::SetPropertyError {
+                property: property.clone(),
+                value: value.to_string(),
+                error: "not found/not supported".to_string(),
+            })?;
+        let conv_value = setter_to_control_value(&descriptor, &property, &value)?;
+        self.device
+            .set_control(Control {
+                id,
+                value: conv_value,
+            })
+            .map_err(|why| NokhwaError::SetPropertyError {
+                property,
+                value: value.to_string(),
+                error: why.to_string(),
+            })
+    }
+
+    /// Opens a new stream and returns it as an asynchronous [`V4LFrameStream`], for use in a `tokio` runtime instead of blocking on [`frame()`](CaptureBackendTrait::frame).
+    ///
+    /// The device is registered with `tokio`'s reactor, and a frame is only dequeued once the driver signals that one is ready.
+    /// Backpressure is handled by the V4L2 buffer queue: while frames are not being polled, the driver fills the [`buffer_count()`](Self::buffer_count) buffers and then drops new frames until one is dequeued again.
+    ///
+    /// An already open stream is restarted. The stream stays open after the [`V4LFrameStream`] is dropped, so [`frame()`](CaptureBackendTrait::frame) can be used again.
+    /// # Errors
+    /// If [`buffer_count()`](Self::buffer_count) is less than 2 (one buffer has to stay queued to the driver while the other is read), or the stream fails to open or start, this will error.
+    /// # Panics
+    /// If this is not called from within a `tokio` runtime with IO enabled, this will panic.
+    #[cfg(feature = "async")]
+    #[cfg_attr(feature = "docs-features", doc(cfg(feature = "async")))]
+    pub fn frame_stream(&mut self) -> Result<V4LFrameStream<'_, 'a>, NokhwaError> {
+        if self.buffer_count < 2 {
+            return Err(NokhwaError::OpenStreamError(format!(
+                "An async stream needs at least 2 buffers, {} are set",
+                self.buffer_count
+            )));
+        }
+        // the stream has to start out without a buffer handed out, which only a new one guarantees
+        self.stream_handle = None;
+        self.open_stream()?;
+        if let Some(stream) = &mut self.stream_handle {
+            stream
+                .start_without_dequeue(self.buffer_count)
+                .map_err(|why| NokhwaError::OpenStreamError(why.to_string()))?;
+        }
+        let fd = AsyncFd::with_interest(self.device.handle().fd(), Interest::READABLE)
+            .map_err(|why| NokhwaError::OpenStreamError(why.to_string()))?;
+        Ok(V4LFrameStream { device: self, fd })
+    }
+
+    /// Returns `false` if the driver does not support setting the frame rate (`VIDIOC_S_PARM`).
+    /// In that case the camera runs at its default frame rate, and the requested frame rate is ignored instead of erroring.
+    #[must_use]
+    pub fn frame_rate_settable(&self) -> bool {
+        self.frame_rate_settable
+    }
+
+    // Compares the (refreshed) current format with `expected`, ignoring the frame rate if the driver does not let us set it.
+    fn matches_camera_format(&self, expected: CameraFormat) -> bool {
+        if self.frame_rate_settable {
+            self.camera_format == expected
+        } else {
+            self.camera_format.resolution() == expected.resolution()
+                && self.camera_format.format() == expected.format()
+        }
+    }
+
     /// Force refreshes the inner [`CameraFormat`] state.
     /// # Errors
     /// If the internal representation in the driver is invalid, this will error.
     pub fn force_refresh_camera_format(&mut self) -> Result<(), NokhwaError> {
         match self.device.format() {
             Ok(format) => {
-                let frame_format =
-                    fourcc_to_frameformat(format.fourcc).ok_or(NokhwaError::GetPropertyError {
-                        property: "FrameFormat".to_string(),
-                        error: "unsupported".to_string(),
-                    })?;
+                let frame_format = fourcc_to_frameformat(format.fourcc)
+                    .unwrap_or(FrameFormat::Custom(format.fourcc.repr));
 
-                let fps = match self.device.params() {
+                let (fps_numerator, fps_denominator) = match self.device.params() {
                     Ok(params) => {
-                        if params.interval.numerator != 1
-                            || params.interval.denominator % params.interval.numerator != 0
-                        {
+                        if params.interval.numerator == 0 {
                             return Err(NokhwaError::GetPropertyError {
                                 property: "V4L2 FrameRate".to_string(),
                                 error: format!(
-                                    "Framerate not whole number: {} / {}",
-                                    params.interval.denominator, params.interval.numerator
+                                    "Invalid frame interval: {} / {}",
+                                    params.interval.numerator, params.interval.denominator
                                 ),
                             });
                         }
-
-                        if params.interval.numerator == 1 {
-                            params.interval.denominator
-                        } else {
-                            params.interval.denominator / params.interval.numerator
-                        }
+                        (params.interval.denominator, params.interval.numerator)
                     }
                     Err(why) => {
                         return Err(NokhwaError::GetPropertyError {
@@ -357,11 +853,13 @@ impl<'a> V4LCaptureDevice<'a> {
                     }
                 };
 
-                self.camera_format = CameraFormat::new(
+                self.camera_format = CameraFormat::new_with_ratio(
                     Resolution::new(format.width, format.height),
                     frame_format,
-                    fps,
+                    fps_numerator,
+                    fps_denominator,
                 );
+                self.colorimetry = Some(format_to_colorimetry(&format, frame_format));
                 Ok(())
             }
             Err(why) => Err(NokhwaError::GetPropertyError {
@@ -370,6 +868,165 @@ impl<'a> V4LCaptureDevice<'a> {
             }),
         }
     }
+
+    /// Same as [`camera_controls()`](CaptureBackendTrait::camera_controls), but each [`CameraControl`] is paired with the raw V4L2 control flags.
+    ///
+    /// The [`KnownCameraControlFlag`]s of a [`CameraControl`] only model some of the flags (e.g. `GRABBED` is not reported at all).
+    /// The raw flags also carry the ones nokhwa does not model, such as `UPDATE`, `SLIDER` or `HAS_PAYLOAD`.
+    /// # Errors
+    /// If the controls cannot be queried, this will error. Controls that fail to read are skipped, as in [`camera_controls()`](CaptureBackendTrait::camera_controls).
+    pub fn camera_controls_with_raw_flags(
+        &self,
+    ) -> Result<Vec<(CameraControl, Flags)>, NokhwaError> {
+        let descriptions =
+            self.device
+                .query_controls()
+                .map_err(|why| NokhwaError::GetPropertyError {
+                    property: "V4L2 Controls".to_string(),
+                    error: why.to_string(),
+                })?;
+
+        Ok(descriptions
+            .into_iter()
+            .filter_map(|desc| {
+                let flags = desc.flags;
+                self.describe_control(desc)
+                    .ok()
+                    .map(|control| (control, flags))
+            })
+            .collect())
+    }
+
+    // The sysfs directory of the USB device the camera is on.
+    fn usb_device_dir(&self) -> Option<PathBuf> {
+        usb_device_dir(self.camera_info.index().as_index().ok()?)
+    }
+
+    // `dequeue_frame()`, copied into a `Buffer` along with the frame's timestamp and sequence number.
+    fn dequeue_buffer(&mut self, timeout: Option<Duration>) -> Result<Buffer, NokhwaError> {
+        let cam_fmt = self.camera_format;
+        let raw_frame = self.dequeue_frame(timeout)?;
+        let buffer = Buffer::new(cam_fmt.resolution(), &raw_frame, cam_fmt.format());
+        Ok(buffer
+            .with_timestamp(self.last_frame_timestamp)
+            .with_sequence(self.last_frame_sequence))
+    }
+
+    // Dequeues the next frame, waiting at most `timeout` (`poll()` on the device before `VIDIOC_DQBUF`), or indefinitely if it is `None`.
+    fn dequeue_frame(&mut self, timeout: Option<Duration>) -> Result<Cow<[u8]>, NokhwaError> {
+        match &mut self.stream_handle {
+            Some(sh) => {
+                // the timeout is passed to `poll()` as milliseconds in an `i32`
+                sh.set_timeout(timeout.map(|timeout| {
+                    timeout.min(Duration::from_millis(i32::MAX.unsigned_abs().into()))
+                }));
+                match sh.next() {
+                    Ok((data, meta)) => {
+                        let timestamp = Duration::from(meta.timestamp);
+                        self.last_frame_timestamp = (!timestamp.is_zero()).then_some(timestamp);
+                        self.last_frame_sequence = Some(meta.sequence);
+                        self.last_frame_bytes = Some(meta.bytesused as usize);
+                        // drivers only flag the frame type for inter-frame compressed formats, any other frame stands on its own
+                        self.last_frame_keyframe = Some(
+                            meta.flags.contains(BufferFlags::KEYFRAME)
+                                || !meta
+                                    .flags
+                                    .intersects(BufferFlags::PFRAME | BufferFlags::BFRAME),
+                        );
+                        Ok(Cow::Borrowed(data))
+                    }
+                    Err(why) => match timeout {
+                        Some(timeout) if why.kind() == io::ErrorKind::TimedOut => {
+                            Err(NokhwaError::TimeoutError(timeout))
+                        }
+                        // the device was unplugged
+                        _ if why.raw_os_error() == Some(libc::ENODEV) => {
+                            Err(NokhwaError::DeviceDisconnected(why.to_string()))
+                        }
+                        _ => Err(NokhwaError::ReadFrameError(why.to_string())),
+                    },
+                }
+            }
+            None => Err(NokhwaError::ReadFrameError(
+                "Stream Not Started".to_string(),
+            )),
+        }
+    }
+
+    // Reads the current value of a single control and builds its `CameraControl`, independently of the other controls.
+    fn describe_control(&self, desc: ControlDescription) -> Result<CameraControl, NokhwaError> {
+        let id_as_kcc = id_to_known_camera_control(desc.id);
+        // buttons have no value to read, and `v4l` can only read single integer and boolean values
+        let ctrl_current = match (desc.typ, self.device.control(desc.id)) {
+            (Type::Button, _) => Value::None,
+            (_, Ok(control)) => control.value,
+            (Type::Integer | Type::Integer64 | Type::Menu | Type::Boolean, Err(why)) => {
+                return Err(NokhwaError::GetPropertyError {
+                    property: desc.name,
+                    error: why.to_string(),
+                })
+            }
+            (_, Err(_)) => Value::None,
+        };
+
+        let ctrl_value_desc = control_value_description(&desc, ctrl_current);
+
+        let is_readonly = desc
+            .flags
+            .intersects(Flags::READ_ONLY)
+            .then_some(KnownCameraControlFlag::ReadOnly);
+        let is_writeonly = desc
+            .flags
+            .intersects(Flags::WRITE_ONLY)
+            .then_some(KnownCameraControlFlag::WriteOnly);
+        let is_disabled = desc
+            .flags
+            .intersects(Flags::DISABLED)
+            .then_some(KnownCameraControlFlag::Disabled);
+        let is_volatile = desc
+            .flags
+            .intersects(Flags::VOLATILE)
+            .then_some(KnownCameraControlFlag::Volatile);
+        let is_inactive = desc
+            .flags
+            .intersects(Flags::INACTIVE)
+            .then_some(KnownCameraControlFlag::Inactive);
+        let flags_vec = vec![
+            is_inactive,
+            is_readonly,
+            is_volatile,
+            is_disabled,
+            is_writeonly,
+        ]
+        .into_iter()
+        .filter(Option::is_some)
+        .collect::<Option<Vec<KnownCameraControlFlag>>>()
+        .unwrap_or_default();
+
+        Ok(CameraControl::new(
+            id_as_kcc,
+            desc.name,
+            ctrl_value_desc,
+            flags_vec,
+            !desc.flags.intersects(Flags::INACTIVE),
+        )
+        .with_unit(control_unit(desc.id).map(ToString::to_string)))
+    }
+}
+
+// The unit of a control's value as defined by the V4L2 spec. `VIDIOC_QUERYCTRL` carries no unit, so this goes by the control ID.
+fn control_unit(id: u32) -> Option<&'static str> {
+    match id {
+        V4L2_CID_EXPOSURE_ABSOLUTE => Some("100µs"),
+        V4L2_CID_WHITE_BALANCE_TEMPERATURE => Some("K"),
+        V4L2_CID_PAN_ABSOLUTE | V4L2_CID_TILT_ABSOLUTE => Some("arcsec"),
+        // the spec leaves these to the driver
+        V4L2_CID_PAN_RELATIVE..=V4L2_CID_TILT_RELATIVE
+        | V4L2_CID_FOCUS_ABSOLUTE..=V4L2_CID_FOCUS_RELATIVE
+        | V4L2_CID_ZOOM_ABSOLUTE..=V4L2_CID_ZOOM_RELATIVE
+        | V4L2_CID_IRIS_ABSOLUTE..=V4L2_CID_IRIS_RELATIVE => Some("steps"),
+        _ => None,
+    }
 }
 
 impl<'a> CaptureBackendTrait for V4LCaptureDevice<'a> {
@@ -409,60 +1066,95 @@ impl<'a> CaptureBackendTrait for V4LCaptureDevice<'a> {
             }
         };
 
-        let v4l_fcc = match new_fmt.format() {
-            FrameFormat::MJPEG => FourCC::new(b"MJPG"),
-            FrameFormat::YUYV => FourCC::new(b"YUYV"),
-            FrameFormat::GRAY => FourCC::new(b"GRAY"),
-            FrameFormat::RAWRGB => FourCC::new(b"RGB3"),
-            FrameFormat::NV12 => FourCC::new(b"NV12"),
-        };
+        let v4l_fcc = frameformat_to_fourcc(new_fmt.format());
 
-        let format = Format::new(new_fmt.width(), new_fmt.height(), v4l_fcc);
-        let frame_rate = Parameters::with_fps(new_fmt.frame_rate());
+        let mut format = Format::new(new_fmt.width(), new_fmt.height(), v4l_fcc);
+        if let Some(size) = self.buffer_size_hint {
+            format.size = size;
+        }
+        let frame_rate = frame_rate_to_parameters(new_fmt);
 
-        if let Err(why) = Capture::set_format(&self.device, &format) {
-            return Err(NokhwaError::SetPropertyError {
+        // a frame rate only change can be applied to the live stream on drivers that allow `VIDIOC_S_PARM` while streaming
+        if self.stream_handle.is_some()
+            && !self.format_change_disruptive(new_fmt)
+            && Capture::set_params(&self.device, &frame_rate).is_ok()
+        {
+            self.force_refresh_camera_format()?;
+            if self.matches_camera_format(new_fmt) {
+                return Ok(());
+            }
+        }
+
+        // V4L2 refuses to change the format while buffers are allocated, so the old stream has to go
+        // before the new format can be set. Everything that can fail without touching the stream is
+        // done first, so the gap between the old and the new stream is as short as possible.
+        let was_streaming = self.stream_handle.is_some();
+        if was_streaming {
+            if !self.compatible_fourcc()?.contains(&new_fmt.format()) {
+                return Err(NokhwaError::SetPropertyError {
+                    property: "Resolution, FrameFormat".to_string(),
+                    value: format.to_string(),
+                    error: "FrameFormat not supported by device".to_string(),
+                });
+            }
+            self.stream_handle = None;
+        }
+
+        let set_result = Capture::set_format(&self.device, &format)
+            .map_err(|why| NokhwaError::SetPropertyError {
                 property: "Resolution, FrameFormat".to_string(),
                 value: format.to_string(),
                 error: why.to_string(),
+            })
+            .and_then(|_| match Capture::set_params(&self.device, &frame_rate) {
+                Ok(_) => Ok(true),
+                Err(why) if is_unsupported_ioctl(&why) => Ok(false),
+                Err(why) => Err(NokhwaError::SetPropertyError {
+                    property: "Frame rate".to_string(),
+                    value: frame_rate.to_string(),
+                    error: why.to_string(),
+                }),
+            })
+            .map(|frame_rate_settable| self.frame_rate_settable = frame_rate_settable)
+            .and_then(|_| {
+                if was_streaming {
+                    self.open_stream()
+                } else {
+                    Ok(())
+                }
             });
-        }
-        if let Err(why) = Capture::set_params(&self.device, &frame_rate) {
-            return Err(NokhwaError::SetPropertyError {
-                property: "Frame rate".to_string(),
-                value: frame_rate.to_string(),
-                error: why.to_string(),
-            });
-        }
 
-        if self.stream_handle.is_some() {
-            return match self.open_stream() {
-                Ok(_) => Ok(()),
-                Err(why) => {
-                    // undo
-                    if let Err(why) = Capture::set_format(&self.device, &prev_format) {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: format!("Attempt undo due to stream acquisition failure with error {}. Resolution, FrameFormat", why),
-                            value: prev_format.to_string(),
-                            error: why.to_string(),
-                        });
-                    }
-                    if let Err(why) = Capture::set_params(&self.device, &prev_fps) {
-                        return Err(NokhwaError::SetPropertyError {
-                            property:
-                            format!("Attempt undo due to stream acquisition failure with error {}. Frame rate", why),
-                            value: prev_fps.to_string(),
-                            error: why.to_string(),
-                        });
-                    }
-                    Err(why)
+        if let Err(why) = set_result {
+            // undo
+            if let Err(undo_why) = Capture::set_format(&self.device, &prev_format) {
+                return Err(NokhwaError::SetPropertyError {
+                    property: format!("Attempt undo due to stream acquisition failure with error {}. Resolution, FrameFormat", why),
+                    value: prev_format.to_string(),
+                    error: undo_why.to_string(),
+                });
+            }
+            if let Err(undo_why) = Capture::set_params(&self.device, &prev_fps) {
+                if !is_unsupported_ioctl(&undo_why) {
+                    return Err(NokhwaError::SetPropertyError {
+                        property: format!(
+                            "Attempt undo due to stream acquisition failure with error {}. Frame rate",
+                            why
+                        ),
+                        value: prev_fps.to_string(),
+                        error: undo_why.to_string(),
+                    });
                 }
-            };
+            }
+            if was_streaming {
+                self.open_stream()?;
+            }
+            return Err(why);
         }
+
         self.camera_format = new_fmt;
 
         self.force_refresh_camera_format()?;
-        if self.camera_format != new_fmt {
+        if !self.matches_camera_format(new_fmt) {
             return Err(NokhwaError::SetPropertyError {
                 property: "CameraFormat".to_string(),
                 value: new_fmt.to_string(),
@@ -473,6 +1165,17 @@ impl<'a> CaptureBackendTrait for V4LCaptureDevice<'a> {
         Ok(())
     }
 
+    fn format_change_disruptive(&self, new_fmt: CameraFormat) -> bool {
+        if self.stream_handle.is_none() || new_fmt == self.camera_format {
+            return false;
+        }
+        let frame_rate_only = new_fmt.resolution() == self.camera_format.resolution()
+            && new_fmt.format() == self.camera_format.format();
+        !(frame_rate_only
+            && self.frame_rate_settable
+            && !DRIVERS_REJECTING_STREAMING_S_PARM.contains(&self.camera_info.description()))
+    }
+
     fn compatible_list_by_resolution(
         &mut self,
         fourcc: FrameFormat,
@@ -490,7 +1193,11 @@ impl<'a> CaptureBackendTrait for V4LCaptureDevice<'a> {
                     for interval in intervals {
                         match interval.interval {
                             FrameIntervalEnum::Discrete(dis) => {
-                                compatible_fps.push(dis.denominator);
+                                if dis.numerator != 0 {
+                                    compatible_fps.push(
+                                        (dis.denominator + dis.numerator / 2) / dis.numerator,
+                                    );
+                                }
                             }
                             FrameIntervalEnum::Stepwise(step) => {
                                 for fstep in (step.min.numerator..step.max.numerator)
@@ -516,15 +1223,38 @@ impl<'a> CaptureBackendTrait for V4LCaptureDevice<'a> {
         Ok(res_map)
     }
 
+    fn true_max_fps(&self, format: FrameFormat, resolution: Resolution) -> Option<u32> {
+        self.device
+            .enum_frameintervals(
+                frameformat_to_fourcc(format),
+                resolution.width(),
+                resolution.height(),
+            )
+            .ok()?
+            .into_iter()
+            .map(|interval| match interval.interval {
+                FrameIntervalEnum::Discrete(dis) => dis,
+                // the shortest interval of a continuous range is the fastest frame rate
+                FrameIntervalEnum::Stepwise(step) => step.min,
+            })
+            .filter(|fraction| fraction.numerator != 0)
+            // rounded to the nearest frame rate, like `compatible_list_by_resolution`
+            .map(|fraction| (fraction.denominator + fraction.numerator / 2) / fraction.numerator)
+            .max()
+    }
+
     fn compatible_fourcc(&mut self) -> Result<Vec<FrameFormat>, NokhwaError> {
         match self.device.enum_formats() {
             Ok(formats) => {
                 let mut frame_format_vec = vec![];
                 for format in formats {
-                    match fourcc_to_frameformat(format.fourcc) {
-                        Some(ff) => frame_format_vec.push(ff),
-                        None => continue,
+                    if !self.use_libv4l && format.flags.contains(FormatFlags::EMULATED) {
+                        continue;
                     }
+                    frame_format_vec.push(
+                        fourcc_to_frameformat(format.fourcc)
+                            .unwrap_or(FrameFormat::Custom(format.fourcc.repr)),
+                    );
                 }
                 frame_format_vec.sort();
                 frame_format_vec.dedup();
@@ -537,6 +1267,29 @@ impl<'a> CaptureBackendTrait for V4LCaptureDevice<'a> {
         }
     }
 
+    fn detailed_formats(&self) -> Vec<FormatInfo> {
+        self.device
+            .enum_formats()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|desc| {
+                FormatInfo::new(
+                    desc.fourcc.str().unwrap_or_default(),
+                    fourcc_to_frameformat(desc.fourcc),
+                    &desc.description,
+                    desc.flags.contains(FormatFlags::COMPRESSED),
+                    desc.flags.contains(FormatFlags::EMULATED),
+                )
+            })
+            .collect()
+    }
+
+    fn current_fourcc(&self) -> Option<[u8; 4]> {
+        Capture::format(&self.device)
+            .ok()
+            .map(|format| format.fourcc.repr)
+    }
+
     fn resolution(&self) -> Resolution {
         self.camera_format.resolution()
     }
@@ -582,97 +1335,60 @@ impl<'a> CaptureBackendTrait for V4LCaptureDevice<'a> {
 
     #[allow(clippy::cast_possible_wrap)]
     fn camera_controls(&self) -> Result<Vec<CameraControl>, NokhwaError> {
-        self.device
-            .query_controls()
-            .map_err(|why| NokhwaError::GetPropertyError {
-                property: "V4L2 Controls".to_string(),
-                error: why.to_string(),
-            })?
-            .into_iter()
-            .map(|desc| {
-                let id_as_kcc = id_to_known_camera_control(desc.id);
-                let ctrl_current = self.device.control(desc.id)?.value;
-
-                let ctrl_value_desc = match (desc.typ, ctrl_current) {
-                    (
-                        Type::Integer
-                        | Type::Integer64
-                        | Type::Menu
-                        | Type::U8
-                        | Type::U16
-                        | Type::U32
-                        | Type::IntegerMenu,
-                        Value::Integer(current),
-                    ) => ControlValueDescription::IntegerRange {
-                        min: desc.minimum as i64,
-                        max: desc.maximum,
-                        value: current,
-                        step: desc.step as i64,
-                        default: desc.default,
-                    },
-                    (Type::Boolean, Value::Boolean(current)) => ControlValueDescription::Boolean {
-                        value: current,
-                        default: desc.default != 0,
-                    },
+        self.camera_controls_partial()
+            .map(|(controls, _failures)| controls)
+    }
 
-                    (Type::String, Value::String(current)) => ControlValueDescription::String {
-                        value: current,
-                        default: None,
-                    },
-                    _ => {
-                        return Err(io::Error::new(
-                            ErrorKind::Unsupported,
-                            "what is this?????? todo: support ig",
-                        ))
-                    }
-                };
+    fn camera_controls_sorted(&self) -> Result<Vec<CameraControl>, NokhwaError> {
+        let mut controls = self.camera_controls()?;
+        // the control class (user, camera, codec, ...) lives in the upper 16 bits of the ID
+        controls.sort_by_key(|control| {
+            let id = known_camera_control_to_id(control.control());
+            (id & V4L2_CTRL_CLASS_MASK, id)
+        });
+        Ok(controls)
+    }
 
-                let is_readonly = desc
-                    .flags
-                    .intersects(Flags::READ_ONLY)
-                    .then_some(KnownCameraControlFlag::ReadOnly);
-                let is_writeonly = desc
-                    .flags
-                    .intersects(Flags::WRITE_ONLY)
-                    .then_some(KnownCameraControlFlag::WriteOnly);
-                let is_disabled = desc
-                    .flags
-                    .intersects(Flags::DISABLED)
-                    .then_some(KnownCameraControlFlag::Disabled);
-                let is_volatile = desc
-                    .flags
-                    .intersects(Flags::VOLATILE)
-                    .then_some(KnownCameraControlFlag::Volatile);
-                let is_inactive = desc
+    fn camera_controls_partial(&self) -> Result<PartialCameraControls, NokhwaError> {
+        let descriptions =
+            self.device
+                .query_controls()
+                .map_err(|why| NokhwaError::GetPropertyError {
+                    property: "V4L2 Controls".to_string(),
+                    error: why.to_string(),
+                })?;
+
+        let mut controls = vec![];
+        let mut failures = vec![];
+        for desc in descriptions {
+            let id_as_kcc = id_to_known_camera_control(desc.id);
+            match self.describe_control(desc) {
+                Ok(control) => controls.push(control),
+                Err(why) => failures.push((id_as_kcc, why)),
+            }
+        }
+        Ok((controls, failures))
+    }
+
+    fn writable_controls(&self) -> Result<Vec<CameraControl>, NokhwaError> {
+        let descriptions =
+            self.device
+                .query_controls()
+                .map_err(|why| NokhwaError::GetPropertyError {
+                    property: "V4L2 Controls".to_string(),
+                    error: why.to_string(),
+                })?;
+
+        // filtering on the description skips reading the values of the controls that are left out
+        Ok(descriptions
+            .into_iter()
+            .filter(|desc| {
+                !desc
                     .flags
-                    .intersects(Flags::INACTIVE)
-                    .then_some(KnownCameraControlFlag::Disabled);
-                let flags_vec = vec![
-                    is_inactive,
-                    is_readonly,
-                    is_volatile,
-                    is_disabled,
-                    is_writeonly,
-                ]
-                .into_iter()
-                .filter(Option::is_some)
-                .collect::<Option<Vec<KnownCameraControlFlag>>>()
-                .unwrap_or_default();
-
-                Ok(CameraControl::new(
-                    id_as_kcc,
-                    desc.name,
-                    ctrl_value_desc,
-                    flags_vec,
-                    !desc.flags.intersects(Flags::INACTIVE),
-                ))
-            })
-            .filter(Result::is_ok)
-            .collect::<Result<Vec<CameraControl>, io::Error>>()
-            .map_err(|x| NokhwaError::GetPropertyError {
-                property: "www".to_string(),
-                error: x.to_string(),
+                    .intersects(Flags::READ_ONLY | Flags::DISABLED | Flags::GRABBED)
             })
+            .filter_map(|desc| self.describe_control(desc).ok())
+            .collect())
     }
 
     fn set_camera_control(
@@ -680,23 +1396,25 @@ impl<'a> CaptureBackendTrait for V4LCaptureDevice<'a> {
         id: KnownCameraControl,
         value: ControlValueSetter,
     ) -> Result<(), NokhwaError> {
-        let conv_value = match value.clone() {
-            ControlValueSetter::None => Value::None,
-            ControlValueSetter::Integer(i) => Value::Integer(i),
-            ControlValueSetter::Boolean(b) => Value::Boolean(b),
-            ControlValueSetter::String(s) => Value::String(s),
-            ControlValueSetter::Bytes(b) => Value::CompoundU8(b),
-            v => {
-                return Err(NokhwaError::SetPropertyError {
-                    property: id.to_string(),
-                    value: v.to_string(),
-                    error: "not supported".to_string(),
-                })
-            }
-        };
+        let control_id = known_camera_control_to_id(id);
+        let descriptor = self
+            .device
+            .query_controls()
+            .map_err(|why| NokhwaError::GetPropertyError {
+                property: "V4L2 Controls".to_string(),
+                error: why.to_string(),
+            })?
+            .into_iter()
+            .find(|desc| desc.id == control_id)
+            .ok_or(NokhwaError::SetPropertyError {
+                property: id.to_string(),
+                value: value.to_string(),
+                error: "not found/not supported".to_string(),
+            })?;
+        let conv_value = setter_to_control_value(&descriptor, &id.to_string(), &value)?;
         self.device
             .set_control(Control {
-                id: known_camera_control_to_id(id),
+                id: control_id,
                 value: conv_value,
             })
             .map_err(|why| NokhwaError::SetPropertyError {
@@ -717,10 +1435,396 @@ impl<'a> CaptureBackendTrait for V4LCaptureDevice<'a> {
         Ok(())
     }
 
+    fn set_camera_controls(
+        &mut self,
+        controls: &[(KnownCameraControl, ControlValueSetter)],
+    ) -> Result<CameraControlResults, NokhwaError> {
+        let descriptors =
+            self.device
+                .query_controls()
+                .map_err(|why| NokhwaError::GetPropertyError {
+                    property: "V4L2 Controls".to_string(),
+                    error: why.to_string(),
+                })?;
+
+        let mut results = controls
+            .iter()
+            .map(|(id, _)| (*id, Ok(())))
+            .collect::<CameraControlResults>();
+        // `VIDIOC_S_EXT_CTRLS` only takes controls of one class at a time
+        // `v4l`'s `Value` cannot be cloned, so the controls are converted again for each attempt
+        let to_control = |position: usize, descriptor: &ControlDescription| {
+            let (id, value) = &controls[position];
+            setter_to_control_value(descriptor, &id.to_string(), value).map(|value| Control {
+                id: descriptor.id,
+                value,
+            })
+        };
+        let mut by_class: HashMap<u32, Vec<(usize, &ControlDescription)>> = HashMap::new();
+        for (position, (id, value)) in controls.iter().enumerate() {
+            let control_id = known_camera_control_to_id(*id);
+            let validated = descriptors
+                .iter()
+                .find(|desc| desc.id == control_id)
+                .ok_or(NokhwaError::SetPropertyError {
+                    property: id.to_string(),
+                    value: value.to_string(),
+                    error: "not found/not supported".to_string(),
+                })
+                .and_then(|descriptor| to_control(position, descriptor).map(|_| descriptor));
+            match validated {
+                Ok(descriptor) => by_class
+                    .entry(control_id & V4L2_CTRL_CLASS_MASK)
+                    .or_default()
+                    .push((position, descriptor)),
+                Err(why) => results[position].1 = Err(why),
+            }
+        }
+
+        for batch in by_class.into_values() {
+            let atomic = self.device.set_controls(
+                batch
+                    .iter()
+                    .filter_map(|(position, descriptor)| to_control(*position, descriptor).ok())
+                    .collect(),
+            );
+            // the driver rejects the whole batch if any control fails, so find out which one(s) by setting them one by one
+            if atomic.is_err() {
+                for (position, descriptor) in batch {
+                    let (id, value) = &controls[position];
+                    if let Err(why) = to_control(position, descriptor).and_then(|control| {
+                        self.device.set_control(control).map_err(|why| {
+                            NokhwaError::SetPropertyError {
+                                property: id.to_string(),
+                                value: value.to_string(),
+                                error: why.to_string(),
+                            }
+                        })
+                    }) {
+                        results[position].1 = Err(why);
+                    }
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    fn reset_camera_control(&mut self, id: KnownCameraControl) -> Result<(), NokhwaError> {
+        let control_id = known_camera_control_to_id(id);
+        let descriptor = self
+            .device
+            .query_controls()
+            .map_err(|why| NokhwaError::GetPropertyError {
+                property: "V4L2 Controls".to_string(),
+                error: why.to_string(),
+            })?
+            .into_iter()
+            .find(|desc| desc.id == control_id)
+            .ok_or(NokhwaError::SetPropertyError {
+                property: id.to_string(),
+                value: "Default".to_string(),
+                error: "not found/not supported".to_string(),
+            })?;
+
+        let default = match descriptor.typ {
+            Type::Boolean => Value::Boolean(descriptor.default != 0),
+            Type::Integer
+            | Type::Integer64
+            | Type::Menu
+            | Type::IntegerMenu
+            | Type::Bitmask
+            | Type::U8
+            | Type::U16
+            | Type::U32 => Value::Integer(descriptor.default),
+            typ => {
+                return Err(NokhwaError::SetPropertyError {
+                    property: id.to_string(),
+                    value: "Default".to_string(),
+                    error: format!("V4L2 control type {typ:?} has no default value"),
+                })
+            }
+        };
+        self.device
+            .set_control(Control {
+                id: control_id,
+                value: default,
+            })
+            .map_err(|why| NokhwaError::SetPropertyError {
+                property: id.to_string(),
+                value: descriptor.default.to_string(),
+                error: why.to_string(),
+            })
+    }
+
+    fn control_dependencies(&self) -> Vec<ControlDependency> {
+        vec![
+            ControlDependency::new(
+                KnownCameraControl::Other(u128::from(V4L2_CID_AUTO_WHITE_BALANCE)),
+                ControlValueSetter::Boolean(false),
+                vec![
+                    KnownCameraControl::WhiteBalance,
+                    KnownCameraControl::Other(u128::from(V4L2_CID_RED_BALANCE)),
+                    KnownCameraControl::Other(u128::from(V4L2_CID_BLUE_BALANCE)),
+                ],
+            ),
+            ControlDependency::new(
+                KnownCameraControl::Other(u128::from(V4L2_CID_EXPOSURE_AUTO)),
+                ControlValueSetter::Integer(V4L2_EXPOSURE_MANUAL),
+                vec![KnownCameraControl::Exposure, KnownCameraControl::Iris],
+            ),
+            ControlDependency::new(
+                KnownCameraControl::Other(u128::from(V4L2_CID_AUTOGAIN)),
+                ControlValueSetter::Boolean(false),
+                vec![KnownCameraControl::Gain],
+            ),
+            ControlDependency::new(
+                KnownCameraControl::Other(u128::from(V4L2_CID_FOCUS_AUTO)),
+                ControlValueSetter::Boolean(false),
+                vec![KnownCameraControl::Focus],
+            ),
+        ]
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn sensor_temperature(&self) -> Option<f32> {
+        let white_balance_id = known_camera_control_to_id(KnownCameraControl::WhiteBalance);
+        let temperature_desc = self
+            .device
+            .query_controls()
+            .ok()?
+            .into_iter()
+            .find(|desc| {
+                let name = desc.name.to_lowercase();
+                desc.id != white_balance_id
+                    && name.contains("temperature")
+                    && !name.contains("white balance")
+            })?;
+
+        match self.device.control(temperature_desc.id).ok()?.value {
+            Value::Integer(temperature) => Some(temperature as f32),
+            _ => None,
+        }
+    }
+
+    fn set_led(&mut self, on: bool) -> Result<(), NokhwaError> {
+        // There is no standard LED control, but UVC vendor extension units (e.g. Logitech's "LED1 Mode") usually have "LED" in their name.
+        let led_desc = self
+            .device
+            .query_controls()
+            .map_err(|why| NokhwaError::GetPropertyError {
+                property: "V4L2 Controls".to_string(),
+                error: why.to_string(),
+            })?
+            .into_iter()
+            .find(|desc| {
+                desc.name
+                    .split(|c: char| !c.is_ascii_alphanumeric())
+                    .any(|word| word.to_ascii_lowercase().starts_with("led"))
+                    && !desc.flags.intersects(Flags::READ_ONLY)
+            })
+            .ok_or(NokhwaError::UnsupportedOperationError(
+                ApiBackend::Video4Linux,
+            ))?;
+
+        // menu-style LED controls (off/on/blink/auto) use 0 for off and 1 for on
+        let value = if matches!(led_desc.typ, Type::Boolean) {
+            Value::Boolean(on)
+        } else {
+            Value::Integer(i64::from(on))
+        };
+        self.device
+            .set_control(Control {
+                id: led_desc.id,
+                value,
+            })
+            .map_err(|why| NokhwaError::SetPropertyError {
+                property: led_desc.name,
+                value: on.to_string(),
+                error: why.to_string(),
+            })
+    }
+
+    fn usb_speed(&self) -> Option<UsbSpeed> {
+        let speed = fs::read_to_string(self.usb_device_dir()?.join("speed")).ok()?;
+
+        match speed.trim() {
+            "1.5" => Some(UsbSpeed::Low),
+            "12" => Some(UsbSpeed::Full),
+            "480" => Some(UsbSpeed::High),
+            mbps => match mbps.parse::<u32>() {
+                Ok(mbps) if mbps >= 5000 => Some(UsbSpeed::Super),
+                _ => None,
+            },
+        }
+    }
+
+    fn hard_reset(&mut self) -> Result<(), NokhwaError> {
+        let usb_device = self
+            .usb_device_dir()
+            .ok_or_else(|| NokhwaError::UnsupportedOperationError(self.backend()))?;
+        let was_streaming = self.stream_handle.is_some();
+        self.stream_handle = None;
+
+        // de-authorizing disconnects the device, authorizing it again makes the kernel enumerate it from scratch
+        let authorized = usb_device.join("authorized");
+        for value in ["0", "1"] {
+            fs::write(&authorized, value).map_err(|why| NokhwaError::SetPropertyError {
+                property: authorized.to_string_lossy().to_string(),
+                value: value.to_string(),
+                error: why.to_string(),
+            })?;
+        }
+
+        // the node may come back under another index, as this still holds the old one open
+        let deadline = Instant::now() + HARD_RESET_TIMEOUT;
+        let (index, device) = loop {
+            if let Some(index) = usb_device_video_index(&usb_device) {
+                if let Ok(device) = Device::new(index as usize) {
+                    break (index, device);
+                }
+            }
+            if Instant::now() > deadline {
+                return Err(NokhwaError::OpenDeviceError(
+                    self.camera_info.index().to_string(),
+                    format!(
+                        "Device did not come back within {HARD_RESET_TIMEOUT:?} after the USB reset"
+                    ),
+                ));
+            }
+            thread::sleep(HARD_RESET_POLL_INTERVAL);
+        };
+        self.device = device;
+        self.camera_info.set_index(CameraIndex::Index(index));
+        self.camera_info.set_device_path(
+            &device_node_path(&PathBuf::from(format!("/dev/video{index}"))).to_string_lossy(),
+        );
+
+        self.set_camera_format(self.camera_format)?;
+        if was_streaming {
+            self.open_stream()?;
+        }
+        Ok(())
+    }
+
+    fn set_frame_rate_range(&mut self, range: FrameRateRange) -> Result<(), NokhwaError> {
+        // Without exposure auto priority, the driver keeps the negotiated frame rate fixed.
+        let has_priority = self
+            .device
+            .query_controls()
+            .map_err(|why| NokhwaError::GetPropertyError {
+                property: "V4L2 Controls".to_string(),
+                error: why.to_string(),
+            })?
+            .iter()
+            .any(|desc| desc.id == V4L2_CID_EXPOSURE_AUTO_PRIORITY);
+        if !has_priority {
+            return Ok(());
+        }
+
+        let allow_drop = range.min() < self.camera_format.frame_rate();
+        self.device
+            .set_control(Control {
+                id: V4L2_CID_EXPOSURE_AUTO_PRIORITY,
+                value: Value::Boolean(allow_drop),
+            })
+            .map_err(|why| NokhwaError::SetPropertyError {
+                property: "Exposure Auto Priority".to_string(),
+                value: range.to_string(),
+                error: why.to_string(),
+            })
+    }
+
+    fn colorimetry(&self) -> Option<Colorimetry> {
+        self.colorimetry
+    }
+
+    fn rolling_shutter_info(&self) -> Option<RollingShutter> {
+        let control_value = |id| match self.device.control(id).ok()?.value {
+            Value::Integer(value) => u64::try_from(value).ok(),
+            _ => None,
+        };
+        // There is no control for the line time itself, but a line takes `width + HBLANK` pixel clocks at `PIXEL_RATE` pixels per second.
+        // These are usually only exposed by sensor drivers (not `uvcvideo`), and assume the sensor is not scaling.
+        let hblank = control_value(V4L2_CID_HBLANK)?;
+        let pixel_rate = control_value(V4L2_CID_PIXEL_RATE).filter(|rate| *rate != 0)?;
+        let line_length = u64::from(self.camera_format.width()) + hblank;
+        // a sensor mounted upside down reads out the bottom line of the image first
+        let top_to_bottom = control_value(V4L2_CID_CAMERA_SENSOR_ROTATION) != Some(180);
+        Some(RollingShutter::new(
+            line_length * 1_000_000_000 / pixel_rate,
+            top_to_bottom,
+        ))
+    }
+
+    fn negotiated_vs_requested(&self) -> Option<FormatNegotiation> {
+        self.negotiation
+    }
+
+    fn set_buffer_size_hint(&mut self, size: Option<u32>) -> Result<(), NokhwaError> {
+        let mut format =
+            Capture::format(&self.device).map_err(|why| NokhwaError::GetPropertyError {
+                property: "Resolution, FrameFormat".to_string(),
+                error: why.to_string(),
+            })?;
+        // a `sizeimage` of 0 lets the driver pick the size again
+        format.size = size.unwrap_or(0);
+
+        // the buffers are allocated with the old size, so they have to be released first
+        let was_streaming = self.stream_handle.take().is_some();
+        if let Err(why) = Capture::set_format(&self.device, &format) {
+            // undo, the driver kept the old format so the old stream can be reopened
+            if was_streaming {
+                self.open_stream()?;
+            }
+            return Err(NokhwaError::SetPropertyError {
+                property: "Buffer Size".to_string(),
+                value: format!("{size:?}"),
+                error: why.to_string(),
+            });
+        }
+        self.buffer_size_hint = size;
+        if was_streaming {
+            self.open_stream()?;
+        }
+        self.force_refresh_camera_format()
+    }
+
+    fn buffer_size(&self) -> Option<u32> {
+        Capture::format(&self.device)
+            .ok()
+            .map(|format| format.size)
+            .filter(|size| *size != 0)
+    }
+
     fn open_stream(&mut self) -> Result<(), NokhwaError> {
-        let stream = match MmapStream::new(&self.device, v4l::buffer::Type::VideoCapture) {
-            Ok(s) => s,
-            Err(why) => return Err(NokhwaError::OpenStreamError(why.to_string())),
+        let buffer_type = v4l::buffer::Type::VideoCapture;
+        // the driver allocates memory mapped buffers on `VIDIOC_REQBUFS`, following the memory policy of the calling thread
+        let _numa_preference = self
+            .numa_node
+            .map(NumaPreference::set)
+            .transpose()
+            .map_err(|why| NokhwaError::OpenStreamError(why.to_string()))?;
+        let stream = match self.stream_method {
+            // drivers without mmap support fail to allocate the buffers, user pointers may still work
+            StreamMethod::Mmap => {
+                match MmapStream::with_buffers(&self.device, buffer_type, self.buffer_count) {
+                    Ok(stream) => StreamHandle::Mmap(stream),
+                    Err(mmap_why) => match UserptrStream::with_buffers(
+                        &self.device,
+                        buffer_type,
+                        self.buffer_count,
+                    ) {
+                        Ok(stream) => StreamHandle::UserPtr(stream),
+                        Err(_) => return Err(NokhwaError::OpenStreamError(mmap_why.to_string())),
+                    },
+                }
+            }
+            StreamMethod::UserPtr => {
+                match UserptrStream::with_buffers(&self.device, buffer_type, self.buffer_count) {
+                    Ok(stream) => StreamHandle::UserPtr(stream),
+                    Err(why) => return Err(NokhwaError::OpenStreamError(why.to_string())),
+                }
+            }
         };
         self.stream_handle = Some(stream);
         Ok(())
@@ -731,25 +1835,31 @@ impl<'a> CaptureBackendTrait for V4LCaptureDevice<'a> {
     }
 
     fn frame(&mut self) -> Result<Buffer, NokhwaError> {
-        let cam_fmt = self.camera_format;
-        let raw_frame = self.frame_raw()?;
-        Ok(Buffer::new(
-            cam_fmt.resolution(),
-            &raw_frame,
-            cam_fmt.format(),
-        ))
+        self.dequeue_buffer(None)
     }
 
     fn frame_raw(&mut self) -> Result<Cow<[u8]>, NokhwaError> {
-        match &mut self.stream_handle {
-            Some(sh) => match sh.next() {
-                Ok((data, _)) => Ok(Cow::Borrowed(data)),
-                Err(why) => Err(NokhwaError::ReadFrameError(why.to_string())),
-            },
-            None => Err(NokhwaError::ReadFrameError(
-                "Stream Not Started".to_string(),
-            )),
-        }
+        self.dequeue_frame(None)
+    }
+
+    fn frame_raw_timeout(&mut self, timeout: Duration) -> Result<Cow<[u8]>, NokhwaError> {
+        self.dequeue_frame(Some(timeout))
+    }
+
+    fn frame_timeout(&mut self, timeout: Duration) -> Result<Buffer, NokhwaError> {
+        self.dequeue_buffer(Some(timeout))
+    }
+
+    fn frame_timestamp(&self) -> Option<Duration> {
+        self.last_frame_timestamp
+    }
+
+    fn frame_is_keyframe(&self) -> Option<bool> {
+        self.last_frame_keyframe
+    }
+
+    fn last_frame_bytes(&self) -> Option<usize> {
+        self.last_frame_bytes
     }
 
     fn stop_stream(&mut self) -> Result<(), NokhwaError> {
@@ -760,13 +1870,546 @@ impl<'a> CaptureBackendTrait for V4LCaptureDevice<'a> {
     }
 }
 
+// Prefers a NUMA node for the memory allocated by (and for) the current thread, restoring the previous memory policy when dropped.
+struct NumaPreference {
+    previous_mode: c_int,
+    previous_mask: [c_ulong; NODE_MASK_WORDS],
+}
+
+impl NumaPreference {
+    fn set(node: u32) -> io::Result<Self> {
+        let mut preference = NumaPreference {
+            previous_mode: MPOL_DEFAULT,
+            previous_mask: [0; NODE_MASK_WORDS],
+        };
+        let no_flags: c_ulong = 0;
+        let got = unsafe {
+            libc::syscall(
+                libc::SYS_get_mempolicy,
+                std::ptr::addr_of_mut!(preference.previous_mode),
+                preference.previous_mask.as_mut_ptr(),
+                NODE_MASK_BITS as c_ulong,
+                std::ptr::null::<c_void>(),
+                no_flags,
+            )
+        };
+        if got != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut mask: [c_ulong; NODE_MASK_WORDS] = [0; NODE_MASK_WORDS];
+        let word_bits = c_ulong::BITS as usize;
+        mask[node as usize / word_bits] |= 1 << (node as usize % word_bits);
+        set_mempolicy(MPOL_PREFERRED, &mask)?;
+        Ok(preference)
+    }
+}
+
+impl Drop for NumaPreference {
+    fn drop(&mut self) {
+        // nothing sensible can be done if this fails, the buffers are allocated by now either way
+        let _ = set_mempolicy(self.previous_mode, &self.previous_mask);
+    }
+}
+
+fn set_mempolicy(mode: c_int, mask: &[c_ulong; NODE_MASK_WORDS]) -> io::Result<()> {
+    // the kernel reads one bit less than `maxnode`
+    let set = unsafe {
+        libc::syscall(
+            libc::SYS_set_mempolicy,
+            mode,
+            mask.as_ptr(),
+            (NODE_MASK_BITS + 1) as c_ulong,
+        )
+    };
+    if set == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// An asynchronous stream of frames from a [`V4LCaptureDevice`], see [`V4LCaptureDevice::frame_stream()`].
+///
+/// It yields a [`Buffer`] for every frame, or an error if a frame fails to dequeue. It never ends on its own.
+#[cfg(feature = "async")]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "async")))]
+pub struct V4LFrameStream<'s, 'a> {
+    device: &'s mut V4LCaptureDevice<'a>,
+    fd: AsyncFd<RawFd>,
+}
+
+#[cfg(feature = "async")]
+impl Stream for V4LFrameStream<'_, '_> {
+    type Item = Result<Buffer, NokhwaError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            let mut guard = match this.fd.poll_read_ready(cx) {
+                Poll::Ready(Ok(guard)) => guard,
+                Poll::Ready(Err(why)) => {
+                    return Poll::Ready(Some(Err(NokhwaError::ReadFrameError(why.to_string()))))
+                }
+                Poll::Pending => return Poll::Pending,
+            };
+            // `tokio` keeps reporting the device as readable until told otherwise, so check that a buffer is actually done before dequeueing,
+            // as `next()` would block otherwise
+            match this.device.device.handle().poll(POLLIN, 0) {
+                Ok(0) => guard.clear_ready(),
+                Ok(_) => return Poll::Ready(Some(this.device.frame())),
+                Err(why) => {
+                    return Poll::Ready(Some(Err(NokhwaError::ReadFrameError(why.to_string()))))
+                }
+            }
+        }
+    }
+}
+
+fn cached_camera_formats(key: &FormatCacheKey) -> Option<Vec<CameraFormat>> {
+    FORMAT_CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()?
+        .get(key)
+        .cloned()
+}
+
+// Does nothing while the cache is disabled.
+fn cache_camera_formats(key: FormatCacheKey, formats: &[CameraFormat]) {
+    if let Some(cache) = FORMAT_CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_mut()
+    {
+        cache.insert(key, formats.to_vec());
+    }
+}
+
+// Lists every format the device offers, the expensive part of opening a device (several ioctls per format and resolution).
+fn enumerate_camera_formats(
+    device: &Device,
+    use_libv4l: bool,
+    max_stepwise_resolutions: usize,
+) -> Result<Vec<CameraFormat>, NokhwaError> {
+    let mut camera_formats = vec![];
+
+    let frame_formats = match device.enum_formats() {
+        Ok(formats) => {
+            let mut frame_format_vec = vec![];
+            formats
+                .iter()
+                .filter(|fmt| use_libv4l || !fmt.flags.contains(FormatFlags::EMULATED))
+                .for_each(|fmt| frame_format_vec.push(fmt.fourcc));
+            frame_format_vec.dedup();
+            Ok(frame_format_vec)
+        }
+        Err(why) => Err(NokhwaError::GetPropertyError {
+            property: "FrameFormat".to_string(),
+            error: why.to_string(),
+        }),
+    }?;
+
+    for ff in frame_formats {
+        // formats nokhwa cannot decode are still listed, so their raw frames can be captured
+        let framefmt = fourcc_to_frameformat(ff).unwrap_or(FrameFormat::Custom(ff.repr));
+        // i write unmaintainable blobs of code because i am so cute uwu~~
+        let mut formats = device
+            .enum_framesizes(ff)
+            .map_err(|why| NokhwaError::GetPropertyError {
+                property: "ResolutionList".to_string(),
+                error: why.to_string(),
+            })?
+            .into_iter()
+            .flat_map(|x| {
+                match x.size {
+                    FrameSizeEnum::Discrete(d) => [Resolution::new(d.width, d.height)].to_vec(),
+                    // we step over each step, getting a new resolution.
+                    FrameSizeEnum::Stepwise(s) => {
+                        sample_stepwise_resolutions(&s, max_stepwise_resolutions)
+                    }
+                }
+            })
+            .flat_map(|res| {
+                device
+                    .enum_frameintervals(ff, res.x(), res.y())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .flat_map(|x| match x.interval {
+                        FrameIntervalEnum::Discrete(dis) => {
+                            // the interval is seconds per frame, so the frame rate is its inverse (e.g. 1001/30000 s is 29.97 FPS)
+                            if dis.numerator == 0 {
+                                vec![]
+                            } else {
+                                vec![CameraFormat::new_with_ratio(
+                                    Resolution::new(x.width, x.height),
+                                    framefmt,
+                                    dis.denominator,
+                                    dis.numerator,
+                                )]
+                            }
+                        }
+                        FrameIntervalEnum::Stepwise(step) => {
+                            let mut intvec = vec![];
+                            for fstep in (step.min.numerator..step.max.numerator)
+                                .step_by(step.step.numerator as usize)
+                            {
+                                if step.max.denominator != 1 || step.min.denominator != 1 {
+                                    intvec.push(CameraFormat::new(
+                                        Resolution::new(x.width, x.height),
+                                        framefmt,
+                                        fstep,
+                                    ));
+                                }
+                            }
+                            intvec
+                        }
+                    })
+            })
+            .collect::<Vec<CameraFormat>>();
+        camera_formats.append(&mut formats);
+    }
+
+    Ok(camera_formats)
+}
+
+// Resolves symlinks (e.g. `/dev/v4l/by-id/...`) to the real node, keeping the path as is if it cannot be resolved.
+pub(crate) fn device_node_path(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+// Another handle streaming from (or holding the buffers of) the device makes `VIDIOC_REQBUFS` fail with `EBUSY`.
+// Requesting 0 buffers allocates nothing, so the probe never claims the queue itself.
+pub(crate) fn device_in_use(device: &Device) -> bool {
+    let mut request = v4l2_requestbuffers {
+        count: 0,
+        type_: v4l::buffer::Type::VideoCapture as u32,
+        memory: Memory::Mmap as u32,
+        ..unsafe { mem::zeroed() }
+    };
+    let result = unsafe {
+        v4l2::ioctl(
+            device.handle().fd(),
+            vidioc::VIDIOC_REQBUFS,
+            std::ptr::addr_of_mut!(request).cast(),
+        )
+    };
+    matches!(result, Err(why) if why.raw_os_error() == Some(libc::EBUSY))
+}
+
+// `VIDIOC_S_PARM` takes the frame interval (seconds per frame), the inverse of the frame rate.
+fn frame_rate_to_parameters(format: CameraFormat) -> Parameters {
+    match format.frame_rate_ratio() {
+        (fps, 1) => Parameters::with_fps(fps),
+        (numerator, denominator) => Parameters::new(Fraction::new(denominator, numerator)),
+    }
+}
+
+// The sysfs directory of the USB device video node `index` is on. The node's `device` points at the USB interface, the `speed` attribute lives on the USB device above it.
+fn usb_device_dir(index: u32) -> Option<PathBuf> {
+    let interface = fs::canonicalize(
+        PathBuf::from("/sys/class/video4linux")
+            .join(format!("video{index}"))
+            .join("device"),
+    )
+    .ok()?;
+    interface
+        .ancestors()
+        .find(|dir| dir.join("speed").is_file())
+        .map(Path::to_path_buf)
+}
+
+// Finds the ALSA capture device on another interface of the same USB device as video node `index`, e.g. a webcam's microphone.
+// The sound card shows up as `<interface>/sound/cardN`, with a `pcmCNDMc` entry for each capture PCM.
+pub(crate) fn associated_audio_device(index: u32) -> Option<String> {
+    fs::read_dir(usb_device_dir(index)?)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter_map(|interface| fs::read_dir(interface.path().join("sound")).ok())
+        .flatten()
+        .filter_map(Result::ok)
+        .find_map(|card| {
+            let card_number = card
+                .file_name()
+                .to_str()?
+                .strip_prefix("card")?
+                .parse::<u32>()
+                .ok()?;
+            let pcm_device = fs::read_dir(card.path())
+                .ok()?
+                .filter_map(Result::ok)
+                .filter_map(|pcm| {
+                    pcm.file_name()
+                        .to_str()?
+                        .strip_prefix(&format!("pcmC{card_number}D"))?
+                        .strip_suffix('c')?
+                        .parse::<u32>()
+                        .ok()
+                })
+                .min()?;
+            // the card ID (e.g. `C920`) stays the same when cards are numbered differently
+            Some(match fs::read_to_string(card.path().join("id")) {
+                Ok(id) => format!("hw:CARD={},DEV={pcm_device}", id.trim()),
+                Err(_) => format!("hw:{card_number},{pcm_device}"),
+            })
+        })
+}
+
+// The lowest video node index below a USB device, e.g. `.../1-1/1-1:1.0/video4linux/video2`. UVC cameras list their capture node before the metadata node.
+fn usb_device_video_index(usb_device: &Path) -> Option<u32> {
+    fs::read_dir(usb_device)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter_map(|interface| fs::read_dir(interface.path().join("video4linux")).ok())
+        .flatten()
+        .filter_map(Result::ok)
+        .filter_map(|node| {
+            node.file_name()
+                .to_str()?
+                .strip_prefix("video")?
+                .parse::<u32>()
+                .ok()
+        })
+        .min()
+}
+
+// Some (UVC) drivers do not implement `VIDIOC_S_PARM`, and answer with `EINVAL` or `ENOTTY`.
+fn is_unsupported_ioctl(why: &io::Error) -> bool {
+    matches!(why.raw_os_error(), Some(libc::EINVAL | libc::ENOTTY))
+}
+
+// Describes a control of type `desc.typ` holding `value`, as read with `VIDIOC_G_EXT_CTRLS`.
+#[allow(clippy::cast_possible_wrap)]
+fn control_value_description(desc: &ControlDescription, value: Value) -> ControlValueDescription {
+    match (desc.typ, value) {
+        (Type::Button, _) => ControlValueDescription::None,
+        (Type::Menu | Type::IntegerMenu, Value::Integer(current)) => {
+            ControlValueDescription::Menu {
+                items: desc
+                    .items
+                    .iter()
+                    .flatten()
+                    .map(|(index, item)| (i64::from(*index), item.to_string()))
+                    .collect(),
+                value: current,
+                default: desc.default,
+            }
+        }
+        (
+            Type::Integer | Type::Integer64 | Type::U8 | Type::U16 | Type::U32,
+            Value::Integer(current),
+        ) => ControlValueDescription::IntegerRange {
+            min: desc.minimum as i64,
+            max: desc.maximum,
+            value: current,
+            step: desc.step as i64,
+            default: desc.default,
+        },
+        (Type::Boolean, Value::Boolean(current)) => ControlValueDescription::Boolean {
+            value: current,
+            default: desc.default != 0,
+        },
+
+        (Type::String, Value::String(current)) => ControlValueDescription::String {
+            value: current,
+            default: None,
+        },
+        (_, Value::CompoundU8(current) | Value::CompoundPtr(current)) => {
+            ControlValueDescription::Bytes {
+                value: current,
+                default: vec![],
+            }
+        }
+        (_, Value::CompoundU16(current)) => ControlValueDescription::Bytes {
+            value: current.iter().flat_map(|v| v.to_ne_bytes()).collect(),
+            default: vec![],
+        },
+        (_, Value::CompoundU32(current)) => ControlValueDescription::Bytes {
+            value: current.iter().flat_map(|v| v.to_ne_bytes()).collect(),
+            default: vec![],
+        },
+        // keep the control listed, so callers at least know it exists
+        (typ, _) => ControlValueDescription::Unknown {
+            kind: format!("V4L2 {typ:?}"),
+        },
+    }
+}
+
+// How a control that is only known by its raw ID is named in errors.
+fn raw_control_name(id: u32) -> String {
+    format!("V4L2 Control {id:#x}")
+}
+
+// Checks `value` against the control's type (and menu items), and converts it for `VIDIOC_S_EXT_CTRLS`.
+fn setter_to_control_value(
+    descriptor: &ControlDescription,
+    property: &str,
+    value: &ControlValueSetter,
+) -> Result<Value, NokhwaError> {
+    if !setter_matches_control_type(descriptor.typ, value) {
+        return Err(NokhwaError::SetPropertyError {
+            property: property.to_string(),
+            value: value.to_string(),
+            error: format!(
+                "Type mismatch: control is of V4L2 type {:?}, which cannot be set with {}",
+                descriptor.typ, value
+            ),
+        });
+    }
+    // drivers may skip indices within the menu's range, reject those with a readable error rather than `EINVAL`
+    if let (Some(items), Some(index)) = (&descriptor.items, value.as_integer()) {
+        if !items
+            .iter()
+            .any(|(item_index, _)| i64::from(*item_index) == *index)
+        {
+            return Err(NokhwaError::SetPropertyError {
+                property: property.to_string(),
+                value: value.to_string(),
+                error: format!(
+                    "Not a menu item, expected one of {:?}",
+                    items.iter().map(|(index, _)| *index).collect::<Vec<u32>>()
+                ),
+            });
+        }
+    }
+
+    match value.clone() {
+        ControlValueSetter::None => Ok(Value::None),
+        ControlValueSetter::Integer(i) => Ok(Value::Integer(i)),
+        ControlValueSetter::Boolean(b) => Ok(Value::Boolean(b)),
+        ControlValueSetter::String(s) => Ok(Value::String(s)),
+        ControlValueSetter::Bytes(b) => Ok(Value::CompoundU8(b)),
+        v => Err(NokhwaError::SetPropertyError {
+            property: property.to_string(),
+            value: v.to_string(),
+            error: "not supported".to_string(),
+        }),
+    }
+}
+
+fn setter_matches_control_type(typ: Type, setter: &ControlValueSetter) -> bool {
+    match typ {
+        Type::Integer | Type::Integer64 | Type::Menu | Type::IntegerMenu | Type::Bitmask => {
+            setter.as_integer().is_some()
+        }
+        Type::Boolean => setter.as_boolean().is_some(),
+        Type::String => setter.as_str().is_some(),
+        Type::Button => setter.as_none().is_some(),
+        Type::U8 | Type::U16 | Type::U32 => {
+            setter.as_integer().is_some() || setter.as_bytes().is_some()
+        }
+        _ => false,
+    }
+}
+
+// Resolves V4L2's `Default` values the same way the kernel's `V4L2_MAP_*_DEFAULT` macros do.
+// `v4l` does not expose `ycbcr_enc`, so the matrix is always the colorspace's default.
+fn format_to_colorimetry(format: &Format, frame_format: FrameFormat) -> Colorimetry {
+    let primaries = match format.colorspace {
+        Colorspace::SMPTE170M | Colorspace::NTSC | Colorspace::EBUTech3212 => ColorPrimaries::Bt601,
+        Colorspace::Rec709 | Colorspace::SRGB | Colorspace::JPEG => ColorPrimaries::Bt709,
+        Colorspace::Rec2020 => ColorPrimaries::Bt2020,
+        Colorspace::DCIP3 => ColorPrimaries::DciP3,
+        Colorspace::OPRGB => ColorPrimaries::AdobeRgb,
+        Colorspace::SMPTE240M => ColorPrimaries::Smpte240M,
+        Colorspace::RAW => ColorPrimaries::Raw,
+        _ => ColorPrimaries::Unknown,
+    };
+    let transfer = match (format.transfer, format.colorspace) {
+        (TransferFunction::Rec709, _)
+        | (
+            TransferFunction::Default,
+            Colorspace::SMPTE170M
+            | Colorspace::NTSC
+            | Colorspace::EBUTech3212
+            | Colorspace::Rec709
+            | Colorspace::Rec2020,
+        ) => ColorTransfer::Bt709,
+        (TransferFunction::SRGB, _)
+        | (TransferFunction::Default, Colorspace::SRGB | Colorspace::JPEG) => ColorTransfer::Srgb,
+        (TransferFunction::OPRGB, _) | (TransferFunction::Default, Colorspace::OPRGB) => {
+            ColorTransfer::AdobeRgb
+        }
+        (TransferFunction::SMPTE240M, _) | (TransferFunction::Default, Colorspace::SMPTE240M) => {
+            ColorTransfer::Smpte240M
+        }
+        (TransferFunction::DCIP3, _) | (TransferFunction::Default, Colorspace::DCIP3) => {
+            ColorTransfer::DciP3
+        }
+        (TransferFunction::None, _) | (TransferFunction::Default, Colorspace::RAW) => {
+            ColorTransfer::Linear
+        }
+        (TransferFunction::SMPTE2084, _) => ColorTransfer::Pq,
+        _ => ColorTransfer::Unknown,
+    };
+    let matrix = match format.colorspace {
+        Colorspace::Rec709 | Colorspace::DCIP3 => ColorMatrix::Bt709,
+        Colorspace::Rec2020 => ColorMatrix::Bt2020,
+        Colorspace::SMPTE240M => ColorMatrix::Smpte240M,
+        Colorspace::RAW => ColorMatrix::Unknown,
+        _ => ColorMatrix::Bt601,
+    };
+    let range = match format.quantization {
+        Quantization::FullRange => ColorRange::Full,
+        Quantization::LimitedRange => ColorRange::Limited,
+        _ => {
+            if frame_format == FrameFormat::RAWRGB || matches!(format.colorspace, Colorspace::JPEG)
+            {
+                ColorRange::Full
+            } else {
+                ColorRange::Limited
+            }
+        }
+    };
+    Colorimetry::new(primaries, transfer, matrix, range)
+}
+
+// Samples up to `max_count` resolutions evenly along a stepwise range, from its smallest to its largest size.
+#[allow(clippy::cast_possible_truncation)]
+fn sample_stepwise_resolutions(stepwise: &FrameSizeStepwise, max_count: usize) -> Vec<Resolution> {
+    let step_width = stepwise.step_width.max(1);
+    let step_height = stepwise.step_height.max(1);
+    let width_steps = u64::from(stepwise.max_width.saturating_sub(stepwise.min_width) / step_width);
+    let height_steps =
+        u64::from(stepwise.max_height.saturating_sub(stepwise.min_height) / step_height);
+    let count = (width_steps.max(height_steps) + 1).min(max_count as u64);
+
+    let mut resolutions = (0..count)
+        .map(|i| {
+            let (width_idx, height_idx) = if count > 1 {
+                (
+                    i * width_steps / (count - 1),
+                    i * height_steps / (count - 1),
+                )
+            } else {
+                (0, 0)
+            };
+            Resolution::new(
+                stepwise.min_width + width_idx as u32 * step_width,
+                stepwise.min_height + height_idx as u32 * step_height,
+            )
+        })
+        .collect::<Vec<Resolution>>();
+    resolutions.dedup();
+    resolutions
+}
+
 fn fourcc_to_frameformat(fourcc: FourCC) -> Option<FrameFormat> {
+    if fourcc.repr == V4L2_PIX_FMT_Y16_BE {
+        return Some(FrameFormat::GRAY16BE);
+    }
     match fourcc.str().ok()? {
         "YUYV" => Some(FrameFormat::YUYV),
+        "UYVY" => Some(FrameFormat::UYVY),
         "MJPG" => Some(FrameFormat::MJPEG),
         "GRAY" => Some(FrameFormat::GRAY),
         "RGB3" => Some(FrameFormat::RAWRGB),
+        "BGR3" => Some(FrameFormat::RAWBGR),
         "NV12" => Some(FrameFormat::NV12),
+        "YU12" => Some(FrameFormat::I420),
+        "H264" => Some(FrameFormat::H264),
+        "HEVC" => Some(FrameFormat::H265),
+        "Y16 " => Some(FrameFormat::GRAY16LE),
         _ => None,
     }
 }
@@ -775,8 +2418,146 @@ fn frameformat_to_fourcc(fourcc: FrameFormat) -> FourCC {
     match fourcc {
         FrameFormat::MJPEG => FourCC::new(b"MJPG"),
         FrameFormat::YUYV => FourCC::new(b"YUYV"),
+        FrameFormat::UYVY => FourCC::new(b"UYVY"),
         FrameFormat::GRAY => FourCC::new(b"GRAY"),
         FrameFormat::RAWRGB => FourCC::new(b"RGB3"),
+        FrameFormat::RAWBGR => FourCC::new(b"BGR3"),
         FrameFormat::NV12 => FourCC::new(b"NV12"),
+        FrameFormat::I420 => FourCC::new(b"YU12"),
+        FrameFormat::H264 => FourCC::new(b"H264"),
+        FrameFormat::H265 => FourCC::new(b"HEVC"),
+        FrameFormat::GRAY16LE => FourCC::new(b"Y16 "),
+        FrameFormat::GRAY16BE => FourCC::new(&V4L2_PIX_FMT_Y16_BE),
+        FrameFormat::Custom(fourcc) => FourCC::new(&fourcc),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nokhwa_core::types::{all_known_camera_controls, frame_formats};
+
+    fn stepwise(min: (u32, u32), max: (u32, u32), step: (u32, u32)) -> FrameSizeStepwise {
+        FrameSizeStepwise {
+            min_width: min.0,
+            max_width: max.0,
+            step_width: step.0,
+            min_height: min.1,
+            max_height: max.1,
+            step_height: step.1,
+        }
+    }
+
+    #[test]
+    fn stepwise_resolutions_are_capped() {
+        let resolutions = sample_stepwise_resolutions(&stepwise((2, 2), (4096, 4096), (1, 1)), 16);
+        assert_eq!(resolutions.len(), 16);
+        assert_eq!(resolutions.first(), Some(&Resolution::new(2, 2)));
+        assert_eq!(resolutions.last(), Some(&Resolution::new(4096, 4096)));
+    }
+
+    #[test]
+    fn stepwise_resolutions_follow_the_step() {
+        let resolutions =
+            sample_stepwise_resolutions(&stepwise((320, 240), (1280, 960), (320, 240)), 16);
+        assert_eq!(
+            resolutions,
+            vec![
+                Resolution::new(320, 240),
+                Resolution::new(640, 480),
+                Resolution::new(960, 720),
+                Resolution::new(1280, 960),
+            ]
+        );
+    }
+
+    #[test]
+    fn stepwise_resolutions_with_zero_step() {
+        let resolutions =
+            sample_stepwise_resolutions(&stepwise((160, 120), (1280, 720), (0, 0)), 8);
+        assert_eq!(resolutions.len(), 8);
+        assert_eq!(resolutions.first(), Some(&Resolution::new(160, 120)));
+        assert_eq!(resolutions.last(), Some(&Resolution::new(1280, 720)));
+
+        let single = sample_stepwise_resolutions(&stepwise((640, 480), (640, 480), (0, 0)), 8);
+        assert_eq!(single, vec![Resolution::new(640, 480)]);
+    }
+
+    #[test]
+    fn frame_formats_round_trip() {
+        for format in frame_formats() {
+            assert_eq!(
+                fourcc_to_frameformat(frameformat_to_fourcc(*format)),
+                Some(*format),
+                "{format}"
+            );
+        }
+        assert_eq!(
+            frameformat_to_fourcc(FrameFormat::Custom(*b"Y10 ")),
+            FourCC::new(b"Y10 ")
+        );
+    }
+
+    #[test]
+    fn known_camera_controls_round_trip() {
+        for control in all_known_camera_controls() {
+            assert_eq!(
+                id_to_known_camera_control(known_camera_control_to_id(control)),
+                control
+            );
+        }
+        assert_eq!(
+            id_to_known_camera_control(known_camera_control_to_id(KnownCameraControl::Other(
+                u128::from(V4L2_CID_FOCUS_AUTO)
+            ))),
+            KnownCameraControl::Other(u128::from(V4L2_CID_FOCUS_AUTO))
+        );
+    }
+
+    fn description(typ: Type) -> ControlDescription {
+        ControlDescription {
+            id: 10_094_850,
+            typ,
+            name: "Test Control".to_string(),
+            minimum: 0,
+            maximum: 255,
+            step: 1,
+            default: 0,
+            flags: Flags::empty(),
+            items: None,
+        }
+    }
+
+    #[test]
+    fn compound_u8_control_is_bytes() {
+        assert_eq!(
+            control_value_description(&description(Type::U8), Value::CompoundU8(vec![1, 2, 3])),
+            ControlValueDescription::Bytes {
+                value: vec![1, 2, 3],
+                default: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn compound_u16_control_is_native_endian_bytes() {
+        let value = vec![0x0102, 0x0304];
+        assert_eq!(
+            control_value_description(&description(Type::U16), Value::CompoundU16(value.clone())),
+            ControlValueDescription::Bytes {
+                value: value.iter().flat_map(|v: &u16| v.to_ne_bytes()).collect(),
+                default: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn unreadable_control_is_unknown() {
+        assert_eq!(
+            control_value_description(&description(Type::Area), Value::None),
+            ControlValueDescription::Unknown {
+                kind: "V4L2 Area".to_string(),
+            }
+        );
     }
 }
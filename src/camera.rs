@@ -14,18 +14,30 @@
  * limitations under the License.
  */
 
+use crate::avi::mux_mjpeg_avi;
+use image::{ImageBuffer, Rgb};
 use nokhwa_core::types::RequestedFormatType;
 use nokhwa_core::{
     buffer::Buffer,
     error::NokhwaError,
     pixel_format::FormatDecoder,
-    traits::CaptureBackendTrait,
+    traits::{CameraControlResults, CaptureBackendTrait, PartialCameraControls},
     types::{
-        ApiBackend, CameraControl, CameraFormat, CameraIndex, CameraInfo, ControlValueSetter,
-        FrameFormat, KnownCameraControl, RequestedFormat, Resolution,
+        ApiBackend, BacklightLevel, CameraControl, CameraFormat, CameraIndex, CameraInfo,
+        CameraProfile, CaptureTiming, Colorimetry, ControlDependency, ControlDiff, ControlNode,
+        ControlValueDescription, ControlValueSetter, DecodeCost, ExposureStrategy, FormatInfo,
+        FormatNegotiation, FrameFormat, FrameRateRange, KnownCameraControl, KnownCameraControlFlag,
+        RegionOfInterest, RequestedFormat, Resolution, RollingShutter, UsbSpeed,
     },
 };
-use std::{borrow::Cow, collections::HashMap};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
 #[cfg(feature = "output-wgpu")]
 use wgpu::{Device as WgpuDevice, Queue as WgpuQueue, Texture as WgpuTexture};
 
@@ -34,6 +46,9 @@ pub struct Camera {
     idx: CameraIndex,
     api: ApiBackend,
     device: Box<dyn CaptureBackendTrait>,
+    settle_frames: u32,
+    frames_to_discard: u32,
+    exposure_strategy: ExposureStrategy,
 }
 
 impl Camera {
@@ -58,9 +73,45 @@ impl Camera {
             idx: index,
             api: backend,
             device: camera_backend,
+            settle_frames: 0,
+            frames_to_discard: 0,
+            exposure_strategy: ExposureStrategy::default(),
         })
     }
 
+    /// Create a new camera from an `index`, trying each of the `formats` in order.
+    ///
+    /// For each [`RequestedFormat`], the camera is created, a test stream is opened, a frame is captured, and the stream is closed again.
+    /// The first configuration that gets through all of that is returned, which is stronger than just checking that the format negotiates.
+    /// # Errors
+    /// If none of the `formats` produce a working stream (or `formats` is empty), this will error with the reasons for each failure.
+    pub fn new_try_formats(
+        index: CameraIndex,
+        formats: &[RequestedFormat],
+    ) -> Result<Self, NokhwaError> {
+        let mut failures = vec![];
+        for format in formats {
+            let attempt = Camera::new(index.clone(), *format).and_then(|mut camera| {
+                camera.open_stream()?;
+                camera.frame_raw()?;
+                camera.stop_stream()?;
+                Ok(camera)
+            });
+            match attempt {
+                Ok(camera) => return Ok(camera),
+                Err(why) => failures.push(format!("{format}: {why}")),
+            }
+        }
+
+        Err(NokhwaError::OpenDeviceError(
+            index.to_string(),
+            format!(
+                "No requested format produced a working stream: [{}]",
+                failures.join(", ")
+            ),
+        ))
+    }
+
     /// Create a new `Camera` from raw values.
     /// # Errors
     /// This will error if you either have a bad platform configuration (e.g. `input-v4l` but not on linux) or the backend cannot create the camera (e.g. permission denied).
@@ -105,6 +156,39 @@ impl Camera {
         Ok(())
     }
 
+    /// Re-initializes the camera at the same index, backend and [`CameraFormat`], e.g. once a camera that was unplugged is back.
+    /// The stream is reopened if it was open before.
+    ///
+    /// Unlike [`set_index()`](Self::set_index), failing to stop the old stream is ignored, as the old device may be gone.
+    /// The camera is only replaced if the device found at the index has the same name, so another camera taking over the index is not picked up instead.
+    /// # Errors
+    /// If no device with the same name is at the index (yet), or it fails to initialize or open its stream, this will error and the old camera is kept.
+    pub fn reconnect(&mut self) -> Result<(), NokhwaError> {
+        let was_open = self.device.is_stream_open();
+        let _stop_stream_err = self.device.stop_stream();
+        let format = self.device.camera_format();
+        let mut new_camera = init_camera(
+            &self.idx,
+            RequestedFormat::with_formats(RequestedFormatType::Exact(format), &[format.format()]),
+            self.api,
+        )?;
+        let name = self.device.camera_info().human_name();
+        if new_camera.camera_info().human_name() != name {
+            return Err(NokhwaError::OpenDeviceError(
+                self.idx.to_string(),
+                format!(
+                    "Expected {name}, found {}",
+                    new_camera.camera_info().human_name()
+                ),
+            ));
+        }
+        if was_open {
+            new_camera.open_stream()?;
+        }
+        self.device = new_camera;
+        Ok(())
+    }
+
     /// Gets the current Camera's backend
     #[must_use]
     pub fn backend(&self) -> ApiBackend {
@@ -168,9 +252,26 @@ impl Camera {
                 error: "Failed to fufill".to_string(),
             })?;
         self.device.set_camera_format(new_format)?;
+        if let Some(range) = request.frame_rate_range() {
+            self.device.set_frame_rate_range(range)?;
+        }
         Ok(new_format)
     }
 
+    /// Lets the driver vary the frame rate inside `range` (see [`FrameRateRange`]), so low light scenes slow down instead of staying at a fixed rate.
+    /// # Errors
+    /// If the backend does not support this or setting the range fails, this will error.
+    pub fn set_frame_rate_range(&mut self, range: FrameRateRange) -> Result<(), NokhwaError> {
+        self.device.set_frame_rate_range(range)
+    }
+
+    /// Returns true if changing to `new_fmt` would interrupt the open stream, e.g. to warn a user before they apply the change.
+    /// Frame rate only changes may not be disruptive on backends that can apply them to a running stream.
+    #[must_use]
+    pub fn format_change_disruptive(&self, new_fmt: CameraFormat) -> bool {
+        self.device.format_change_disruptive(new_fmt)
+    }
+
     #[deprecated(since = "0.10.0", note = "please use `set_camera_requset` instead.")]
     /// Will set the current [`CameraFormat`]
     /// This will reset the current stream if used while stream is opened.
@@ -192,6 +293,22 @@ impl Camera {
         self.device.compatible_list_by_resolution(fourcc)
     }
 
+    /// A hashmap of [`Resolution`]s mapped to the highest framerate available at that resolution.
+    /// # Errors
+    /// This will error if the camera is not queryable or a query operation has failed. Some backends will error this out as a [`UnsupportedOperationError`](crate::NokhwaError::UnsupportedOperationError).
+    pub fn max_fps_table(
+        &mut self,
+        fourcc: FrameFormat,
+    ) -> Result<HashMap<Resolution, u32>, NokhwaError> {
+        self.device.max_fps_table(fourcc)
+    }
+
+    /// Gets the true highest frame rate of `format` at `resolution` from the shortest frame interval the driver reports, including for continuous-interval cameras.
+    #[must_use]
+    pub fn true_max_fps(&self, format: FrameFormat, resolution: Resolution) -> Option<u32> {
+        self.device.true_max_fps(format, resolution)
+    }
+
     /// A Vector of compatible [`FrameFormat`]s.
     /// # Errors
     /// This will error if the camera is not queryable or a query operation has failed. Some backends will error this out as a [`UnsupportedOperationError`](crate::NokhwaError::UnsupportedOperationError).
@@ -199,6 +316,12 @@ impl Camera {
         self.device.compatible_fourcc()
     }
 
+    /// A Vector of the pixel formats reported by the driver, with their descriptions and whether they are compressed or emulated. Returns an empty list if the backend does not support this.
+    #[must_use]
+    pub fn detailed_formats(&self) -> Vec<FormatInfo> {
+        self.device.detailed_formats()
+    }
+
     /// A Vector of available [`CameraFormat`]s.
     /// # Errors
     /// This will error if the camera is not queryable or a query operation has failed. Some backends will error this out as a [`UnsupportedOperationError`](crate::NokhwaError::UnsupportedOperationError).
@@ -244,6 +367,67 @@ impl Camera {
         self.device.frame_format()
     }
 
+    /// Gets a rough estimate of the CPU cost of decoding the current [`FrameFormat`]. See [`DecodeCost`].
+    #[must_use]
+    pub fn decode_cost_hint(&self) -> DecodeCost {
+        self.device.frame_format().decode_cost()
+    }
+
+    /// Captures `frames` frames and decodes each with the [`FormatDecoder`] `F`, returning the average time one decode took.
+    /// Only the decoding is timed, not the capture.
+    /// # Errors
+    /// If the backend fails to get a frame or decoding fails, this will error.
+    pub fn measure_decode_cost<F: FormatDecoder>(
+        &mut self,
+        frames: usize,
+    ) -> Result<Duration, NokhwaError> {
+        self.discard_settling_frames()?;
+        let mut total = Duration::ZERO;
+        for _ in 0..frames {
+            let frame = self.device.frame()?;
+            let start = Instant::now();
+            frame.decode_image::<F>()?;
+            total += start.elapsed();
+        }
+        Ok(total / u32::try_from(frames.max(1)).unwrap_or(u32::MAX))
+    }
+
+    /// Captures `frames` frames and decodes each with the [`FormatDecoder`] `F`, timing the capture and the decode separately.
+    /// The returned [`CaptureTiming`] holds the average of each, showing whether the capture (e.g. USB bandwidth) or the decode is the bottleneck.
+    /// # Errors
+    /// If the backend fails to get a frame or decoding fails, this will error.
+    pub fn timing_breakdown<F: FormatDecoder>(
+        &mut self,
+        frames: usize,
+    ) -> Result<CaptureTiming, NokhwaError> {
+        self.discard_settling_frames()?;
+        let (mut capture, mut decode) = (Duration::ZERO, Duration::ZERO);
+        for _ in 0..frames {
+            let start = Instant::now();
+            let frame = self.device.frame()?;
+            capture += start.elapsed();
+
+            let start = Instant::now();
+            frame.decode_image::<F>()?;
+            decode += start.elapsed();
+        }
+        let frames = u32::try_from(frames.max(1)).unwrap_or(u32::MAX);
+        Ok(CaptureTiming::new(capture / frames, decode / frames))
+    }
+
+    /// Gets the four character code of the pixel format the driver is actually using, e.g. `*b"YUYV"`. Returns `None` if the backend does not report it.
+    #[must_use]
+    pub fn current_fourcc(&self) -> Option<[u8; 4]> {
+        self.device.current_fourcc()
+    }
+
+    /// Same as [`current_fourcc()`](Self::current_fourcc), as a string for logging. Non-printable bytes (e.g. the big endian flag) are escaped, as in `Y16\xa0`.
+    #[must_use]
+    pub fn current_fourcc_string(&self) -> Option<String> {
+        self.current_fourcc()
+            .map(|fourcc| fourcc.escape_ascii().to_string())
+    }
+
     /// Will set the current [`FrameFormat`]
     /// This will reset the current stream if used while stream is opened.
     ///
@@ -266,6 +450,28 @@ impl Camera {
             .collect())
     }
 
+    /// Gets the current supported list of [`CameraControl`]s in a deterministic order (for V4L2, grouped by control class, then by ID).
+    /// # Errors
+    /// If the list cannot be collected, this will error. This can be treated as a "nothing supported".
+    pub fn camera_controls_sorted(&self) -> Result<Vec<CameraControl>, NokhwaError> {
+        self.device.camera_controls_sorted()
+    }
+
+    /// Gets the current supported list of [`CameraControl`]s, along with the controls that could not be read and why.
+    /// Unlike [`camera_controls()`](Self::camera_controls), failures are reported instead of silently dropped.
+    /// # Errors
+    /// If the list of controls cannot be queried at all, this will error.
+    pub fn camera_controls_partial(&self) -> Result<PartialCameraControls, NokhwaError> {
+        self.device.camera_controls_partial()
+    }
+
+    /// Gets the supported [`CameraControl`]s that can currently be changed, leaving out read-only, disabled, and (on V4L2) grabbed controls.
+    /// # Errors
+    /// If the list cannot be collected, this will error.
+    pub fn writable_controls(&self) -> Result<Vec<CameraControl>, NokhwaError> {
+        self.device.writable_controls()
+    }
+
     /// Gets the current supported list of [`CameraControl`]s keyed by its name as a `String`.
     /// # Errors
     /// If the list cannot be collected, this will error. This can be treated as a "nothing supported".
@@ -345,7 +551,582 @@ impl Camera {
         id: KnownCameraControl,
         value: ControlValueSetter,
     ) -> Result<(), NokhwaError> {
-        self.device.set_camera_control(id, value)
+        self.device.set_camera_control(id, value)?;
+        self.frames_to_discard = self.settle_frames;
+        Ok(())
+    }
+
+    /// Gets the number of frames discarded after a control change. See [`set_settle_frames()`](Self::set_settle_frames).
+    #[must_use]
+    pub fn settle_frames(&self) -> u32 {
+        self.settle_frames
+    }
+
+    /// Sets the number of frames to discard after a control is changed, before the next frame is returned.
+    ///
+    /// After e.g. an exposure or white balance change, the next few frames are still in flight with the old setting or are transitional.
+    /// With this set, the first frame returned after [`set_camera_control()`](Self::set_camera_control) (or [`set_white_balance_manual()`](Self::set_white_balance_manual)) has the new setting fully applied.
+    /// The default is `0`.
+    pub fn set_settle_frames(&mut self, settle_frames: u32) {
+        self.settle_frames = settle_frames;
+    }
+
+    // Drops the frames still pending from the last control change, see `set_settle_frames`.
+    fn discard_settling_frames(&mut self) -> Result<(), NokhwaError> {
+        while self.frames_to_discard > 0 {
+            self.device.frame_raw()?;
+            self.frames_to_discard -= 1;
+        }
+        Ok(())
+    }
+
+    /// Sets several controls in one call, atomically where the driver supports it. The values are not read back to verify them.
+    /// Returns the result of each control, in the order of `controls`.
+    /// # Errors
+    /// If the controls cannot be queried, this will error.
+    pub fn set_camera_controls(
+        &mut self,
+        controls: &[(KnownCameraControl, ControlValueSetter)],
+    ) -> Result<CameraControlResults, NokhwaError> {
+        let results = self.device.set_camera_controls(controls)?;
+        self.frames_to_discard = self.settle_frames;
+        Ok(results)
+    }
+
+    /// Sets the control back to its default value.
+    /// # Errors
+    /// If the `control` is not supported, has no known default, or there was an error setting the control, this will error.
+    pub fn reset_camera_control(&mut self, id: KnownCameraControl) -> Result<(), NokhwaError> {
+        self.device.reset_camera_control(id)?;
+        self.frames_to_discard = self.settle_frames;
+        Ok(())
+    }
+
+    /// Resets every writable control to its default value. Returns the controls that failed to reset, with the reason why.
+    /// # Errors
+    /// If the list of controls cannot be collected, this will error.
+    pub fn reset_all_controls(
+        &mut self,
+    ) -> Result<Vec<(KnownCameraControl, NokhwaError)>, NokhwaError> {
+        let failed = self.device.reset_all_controls()?;
+        self.frames_to_discard = self.settle_frames;
+        Ok(failed)
+    }
+
+    /// Gets the groups of controls that must change together. See [`ControlDependency`].
+    #[must_use]
+    pub fn control_dependencies(&self) -> Vec<ControlDependency> {
+        self.device.control_dependencies()
+    }
+
+    /// Gets the supported controls as a tree, with auto-mode controls listing their dependent manual controls as children. See [`ControlNode`].
+    #[must_use]
+    pub fn control_tree(&self) -> Vec<ControlNode> {
+        self.device.control_tree()
+    }
+
+    /// Compares the camera's current control values against a saved `profile`, returning a [`ControlDiff`] for every control that differs.
+    ///
+    /// Controls that cannot be read from the camera are reported with no current value.
+    #[must_use]
+    pub fn diff_controls(
+        &self,
+        profile: &[(KnownCameraControl, ControlValueSetter)],
+    ) -> Vec<ControlDiff> {
+        profile
+            .iter()
+            .filter_map(|(control, saved)| {
+                let current = self.camera_control(*control).ok().map(|c| c.value());
+                if current.as_ref() == Some(saved) {
+                    None
+                } else {
+                    Some(ControlDiff::new(*control, saved.clone(), current))
+                }
+            })
+            .collect()
+    }
+
+    /// Captures the camera's current settings as a [`CameraProfile`]: its identifier, [`CameraFormat`], and the values of all writable, active controls.
+    /// Controls without a value (e.g. buttons) are left out, so applying the profile does not trigger them.
+    /// # Errors
+    /// If the controls cannot be read, this will error.
+    pub fn profile(&self) -> Result<CameraProfile, NokhwaError> {
+        let controls = profile_controls(self.device.camera_controls()?);
+        Ok(CameraProfile::new(
+            CameraProfile::identifier_for(self.device.camera_info()),
+            self.device.camera_format(),
+            controls,
+        ))
+    }
+
+    /// Reapplies a [`CameraProfile`]: the [`CameraFormat`] first, then every saved control.
+    /// Auto-mode controls (see [`control_dependencies()`](Self::control_dependencies)) are set before the manual controls that depend on them.
+    ///
+    /// Controls that fail to be set do not stop the rest from being applied. They are returned with their error, like in [`camera_controls_partial()`](Self::camera_controls_partial).
+    /// # Errors
+    /// If the profile was saved from another camera (see [`CameraProfile::matches()`]), or the format cannot be set, this will error.
+    pub fn apply_profile(
+        &mut self,
+        profile: &CameraProfile,
+    ) -> Result<Vec<(KnownCameraControl, NokhwaError)>, NokhwaError> {
+        if !profile.matches(self.device.camera_info()) {
+            return Err(NokhwaError::SetPropertyError {
+                property: "CameraProfile".to_string(),
+                value: profile.identifier().to_string(),
+                error: format!(
+                    "Profile belongs to another camera, this is {}",
+                    CameraProfile::identifier_for(self.device.camera_info())
+                ),
+            });
+        }
+        if self.device.camera_format() != profile.format() {
+            self.set_camera_format(profile.format())?;
+        }
+
+        let parents = self
+            .device
+            .control_dependencies()
+            .iter()
+            .map(ControlDependency::parent)
+            .collect::<Vec<KnownCameraControl>>();
+        let mut controls = profile.controls().to_vec();
+        controls.sort_by_key(|(control, _)| !parents.contains(control));
+
+        let mut failures = vec![];
+        for (control, value) in controls {
+            if let Err(why) = self.set_camera_control(control, value) {
+                failures.push((control, why));
+            }
+        }
+        Ok(failures)
+    }
+
+    /// Sets `format` and then `controls` as one operation, e.g. for reproducible captures.
+    /// Auto-mode controls (see [`control_dependencies()`](Self::control_dependencies)) are set before the manual controls that depend on them.
+    ///
+    /// If the format or any control fails to be set, the camera is rolled back to its previous [`CameraFormat`] and control values (see [`profile()`](Self::profile)) before the error is returned.
+    /// # Errors
+    /// If the current settings cannot be read, or the format or a control cannot be set, this will error. If the rollback fails as well, the error says why.
+    pub fn apply_configuration(
+        &mut self,
+        format: CameraFormat,
+        controls: &[(KnownCameraControl, ControlValueSetter)],
+    ) -> Result<(), NokhwaError> {
+        let previous = self.profile()?;
+        let was_open = self.device.is_stream_open();
+        match self.try_apply_configuration(format, controls) {
+            Ok(()) => {
+                self.frames_to_discard = self.settle_frames;
+                Ok(())
+            }
+            Err(why) => match self.restore_configuration(&previous, was_open) {
+                Ok(()) => Err(why),
+                Err(rollback_why) => Err(NokhwaError::SetPropertyError {
+                    property: "Configuration".to_string(),
+                    value: format.to_string(),
+                    error: format!("{why}, and rolling back failed: {rollback_why}"),
+                }),
+            },
+        }
+    }
+
+    // Sets the format (if it differs) and then the controls, auto modes first, stopping at the first failure.
+    fn try_apply_configuration(
+        &mut self,
+        format: CameraFormat,
+        controls: &[(KnownCameraControl, ControlValueSetter)],
+    ) -> Result<(), NokhwaError> {
+        if self.device.camera_format() != format {
+            self.device.set_camera_format(format)?;
+        }
+
+        let parents = self
+            .device
+            .control_dependencies()
+            .iter()
+            .map(ControlDependency::parent)
+            .collect::<Vec<KnownCameraControl>>();
+        let mut controls = controls.to_vec();
+        controls.sort_by_key(|(control, _)| !parents.contains(control));
+        for (control, value) in controls {
+            self.device.set_camera_control(control, value)?;
+        }
+        Ok(())
+    }
+
+    /// Saves the camera's current settings (see [`profile()`](Self::profile)) to `path` as JSON.
+    /// # Errors
+    /// If the controls cannot be read or the file cannot be written, this will error.
+    #[cfg(feature = "serialize")]
+    #[cfg_attr(feature = "docs-features", doc(cfg(feature = "serialize")))]
+    pub fn save_profile(&self, path: &Path) -> Result<(), NokhwaError> {
+        let json = serde_json::to_string_pretty(&self.profile()?).map_err(|why| {
+            NokhwaError::StructureError {
+                structure: "CameraProfile".to_string(),
+                error: why.to_string(),
+            }
+        })?;
+        std::fs::write(path, json).map_err(|why| {
+            NokhwaError::GeneralError(format!("Failed to write {}: {why}", path.display()))
+        })
+    }
+
+    /// Loads a profile saved by [`save_profile()`](Self::save_profile) from `path` and applies it, see [`apply_profile()`](Self::apply_profile).
+    /// # Errors
+    /// If the file cannot be read or parsed, it belongs to another camera, or the format cannot be set, this will error.
+    #[cfg(feature = "serialize")]
+    #[cfg_attr(feature = "docs-features", doc(cfg(feature = "serialize")))]
+    pub fn load_profile(
+        &mut self,
+        path: &Path,
+    ) -> Result<Vec<(KnownCameraControl, NokhwaError)>, NokhwaError> {
+        let json = std::fs::read_to_string(path).map_err(|why| {
+            NokhwaError::GeneralError(format!("Failed to read {}: {why}", path.display()))
+        })?;
+        let profile = serde_json::from_str::<CameraProfile>(&json).map_err(|why| {
+            NokhwaError::StructureError {
+                structure: "CameraProfile".to_string(),
+                error: why.to_string(),
+            }
+        })?;
+        self.apply_profile(&profile)
+    }
+
+    /// Switches white balance to manual and sets the white balance temperature to `temp_kelvin`, as one operation.
+    ///
+    /// This disables the automatic white balance (if the backend reports one, see [`control_dependencies()`](Self::control_dependencies)),
+    /// sets [`KnownCameraControl::WhiteBalance`], and verifies the new value. If setting the temperature fails, the automatic white balance is restored.
+    /// # Errors
+    /// If the controls are not supported, the value is rejected, or the value does not stick, this will error.
+    pub fn set_white_balance_manual(&mut self, temp_kelvin: u32) -> Result<(), NokhwaError> {
+        let auto_white_balance = self
+            .device
+            .control_dependencies()
+            .into_iter()
+            .find(|dep| dep.dependents().contains(&KnownCameraControl::WhiteBalance));
+
+        let previous_auto = match &auto_white_balance {
+            Some(dep) => {
+                let previous = self.device.camera_control(dep.parent())?.value();
+                self.device
+                    .set_camera_control(dep.parent(), dep.manual_value().clone())?;
+                Some((dep.parent(), previous))
+            }
+            None => None,
+        };
+
+        let temperature = ControlValueSetter::Integer(i64::from(temp_kelvin));
+        let result = self
+            .device
+            .set_camera_control(KnownCameraControl::WhiteBalance, temperature.clone())
+            .and_then(|_| {
+                let current = self
+                    .device
+                    .camera_control(KnownCameraControl::WhiteBalance)?
+                    .value();
+                if current == temperature {
+                    Ok(())
+                } else {
+                    Err(NokhwaError::SetPropertyError {
+                        property: KnownCameraControl::WhiteBalance.to_string(),
+                        value: temperature.to_string(),
+                        error: format!("Rejected, current value is {current}"),
+                    })
+                }
+            });
+
+        if result.is_err() {
+            if let Some((parent, previous)) = previous_auto {
+                let _undo = self.device.set_camera_control(parent, previous);
+            }
+        }
+        self.frames_to_discard = self.settle_frames;
+        result
+    }
+
+    /// Switches white balance to manual and sets the white balance temperature to `kelvin`, clamped (and rounded to the step) within the range the camera supports.
+    /// Returns the temperature in Kelvin that was applied. See [`set_white_balance_manual()`](Self::set_white_balance_manual).
+    /// # Errors
+    /// If the controls are not supported, or the value is rejected or does not stick, this will error.
+    pub fn set_white_balance_kelvin(&mut self, kelvin: u32) -> Result<u32, NokhwaError> {
+        let requested = i64::from(kelvin);
+        let applied = match self
+            .device
+            .camera_control(KnownCameraControl::WhiteBalance)?
+            .description()
+        {
+            ControlValueDescription::IntegerRange { min, max, step, .. } => {
+                let clamped = requested.clamp(*min, *max);
+                if *step > 1 {
+                    (min + (clamped - min + step / 2) / step * step).min(*max)
+                } else {
+                    clamped
+                }
+            }
+            _ => requested,
+        };
+        let applied = u32::try_from(applied).map_err(|why| NokhwaError::SetPropertyError {
+            property: KnownCameraControl::WhiteBalance.to_string(),
+            value: applied.to_string(),
+            error: why.to_string(),
+        })?;
+        self.set_white_balance_manual(applied)?;
+        Ok(applied)
+    }
+
+    /// Gets the current white balance temperature in Kelvin.
+    /// # Errors
+    /// If the control is not supported or does not hold a non-negative integer, this will error.
+    pub fn white_balance_kelvin(&self) -> Result<u32, NokhwaError> {
+        match self
+            .device
+            .camera_control(KnownCameraControl::WhiteBalance)?
+            .value()
+        {
+            ControlValueSetter::Integer(kelvin) => {
+                u32::try_from(kelvin).map_err(|why| NokhwaError::GetPropertyError {
+                    property: KnownCameraControl::WhiteBalance.to_string(),
+                    error: why.to_string(),
+                })
+            }
+            value => Err(NokhwaError::GetPropertyError {
+                property: KnownCameraControl::WhiteBalance.to_string(),
+                error: format!("Expected an integer temperature, got {value}"),
+            }),
+        }
+    }
+
+    /// Sets the backlight compensation to a [`BacklightLevel`], mapped onto the range of [`KnownCameraControl::BacklightComp`] the camera reports.
+    /// # Errors
+    /// If the camera does not support backlight compensation or the value is rejected, this will error.
+    pub fn set_backlight_compensation(&mut self, level: BacklightLevel) -> Result<(), NokhwaError> {
+        let value = match self
+            .device
+            .camera_control(KnownCameraControl::BacklightComp)?
+            .description()
+        {
+            ControlValueDescription::Boolean { .. } => {
+                ControlValueSetter::Boolean(level != BacklightLevel::Off)
+            }
+            ControlValueDescription::IntegerRange { min, max, step, .. } => {
+                let value = match level {
+                    BacklightLevel::Off => *min,
+                    BacklightLevel::Low => {
+                        let step = (*step).max(1);
+                        let half = ((max - min) / 2 / step * step).max(step);
+                        (min + half).min(*max)
+                    }
+                    BacklightLevel::High => *max,
+                };
+                ControlValueSetter::Integer(value)
+            }
+            description => {
+                return Err(NokhwaError::SetPropertyError {
+                    property: KnownCameraControl::BacklightComp.to_string(),
+                    value: level.to_string(),
+                    error: format!("Unexpected control type {description}"),
+                })
+            }
+        };
+        self.set_camera_control(KnownCameraControl::BacklightComp, value)
+    }
+
+    /// Gets the current backlight compensation as a [`BacklightLevel`]. Any value between the minimum and the maximum counts as [`BacklightLevel::Low`].
+    /// # Errors
+    /// If the camera does not support backlight compensation, this will error.
+    pub fn backlight_compensation(&self) -> Result<BacklightLevel, NokhwaError> {
+        match self
+            .device
+            .camera_control(KnownCameraControl::BacklightComp)?
+            .description()
+        {
+            ControlValueDescription::Boolean { value, .. } => Ok(if *value {
+                BacklightLevel::High
+            } else {
+                BacklightLevel::Off
+            }),
+            ControlValueDescription::IntegerRange {
+                min, max, value, ..
+            } => Ok(if value <= min {
+                BacklightLevel::Off
+            } else if value >= max {
+                BacklightLevel::High
+            } else {
+                BacklightLevel::Low
+            }),
+            description => Err(NokhwaError::GetPropertyError {
+                property: KnownCameraControl::BacklightComp.to_string(),
+                error: format!("Unexpected control type {description}"),
+            }),
+        }
+    }
+
+    /// Gets the [`ExposureStrategy`] used by [`set_ev()`](Self::set_ev).
+    #[must_use]
+    pub fn exposure_strategy(&self) -> ExposureStrategy {
+        self.exposure_strategy
+    }
+
+    /// Sets the [`ExposureStrategy`] used by [`set_ev()`](Self::set_ev). The default is [`ExposureStrategy::ExposureFirst`] without a cap.
+    pub fn set_exposure_strategy(&mut self, strategy: ExposureStrategy) {
+        self.exposure_strategy = strategy;
+    }
+
+    // Gets the (value, min, max) of an integer range control.
+    fn integer_control_range(&self, control: KnownCameraControl) -> Option<(i64, i64, i64)> {
+        match self.device.camera_control(control).ok()?.description() {
+            ControlValueDescription::IntegerRange {
+                value, min, max, ..
+            } => Some((*value, *min, *max)),
+            _ => None,
+        }
+    }
+
+    /// Gets the current total exposure as an EV (exposure value), `log2(exposure × gain)`.
+    ///
+    /// Exposure and gain are taken in the camera's raw control units, with gain counted from `1` at its minimum (or `1` if the camera has no gain control),
+    /// so the EV is specific to the camera but comparable between calls: `+1` EV is twice as much light.
+    /// Returns `None` if the camera has no integer range [`KnownCameraControl::Exposure`] control.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn current_ev(&self) -> Option<f32> {
+        let (exposure, _, _) = self.integer_control_range(KnownCameraControl::Exposure)?;
+        let gain_factor = self
+            .integer_control_range(KnownCameraControl::Gain)
+            .map_or(1, |(gain, min, _)| gain - min + 1);
+        let total = exposure * gain_factor;
+        (total > 0).then(|| (total as f32).log2())
+    }
+
+    /// Switches exposure and gain to manual and sets them so the total exposure matches `ev` (see [`current_ev()`](Self::current_ev)),
+    /// split between the two according to the [`ExposureStrategy`].
+    ///
+    /// Both controls are clamped to what the camera supports, so the returned EV that was actually applied may differ from `ev`.
+    /// # Errors
+    /// If the camera has no integer range [`KnownCameraControl::Exposure`] control, or setting a control fails, this will error.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn set_ev(&mut self, ev: f32) -> Result<f32, NokhwaError> {
+        let (_, exposure_min, exposure_max) = self
+            .integer_control_range(KnownCameraControl::Exposure)
+            .ok_or_else(|| NokhwaError::SetPropertyError {
+                property: "EV".to_string(),
+                value: ev.to_string(),
+                error: "Exposure is not supported as an integer range".to_string(),
+            })?;
+        let gain_min = self
+            .integer_control_range(KnownCameraControl::Gain)
+            .map(|(_, min, max)| (min, max - min + 1));
+        let max_gain_factor = gain_min.map_or(1, |(_, max_gain_factor)| max_gain_factor);
+        let exposure_min = exposure_min.max(1);
+
+        let total = f64::from(ev).exp2();
+        let clamp_round = |value: f64, min: i64, max: i64| (value.round() as i64).clamp(min, max);
+        let (exposure, gain_factor) = match self.exposure_strategy {
+            ExposureStrategy::ExposureFirst { max_exposure } => {
+                let cap =
+                    max_exposure.map_or(exposure_max, |cap| cap.clamp(exposure_min, exposure_max));
+                let exposure = clamp_round(total, exposure_min, cap);
+                let gain_factor = clamp_round(total / exposure as f64, 1, max_gain_factor);
+                (exposure, gain_factor)
+            }
+            ExposureStrategy::GainFirst => {
+                let gain_factor = clamp_round(total / exposure_min as f64, 1, max_gain_factor);
+                let exposure = clamp_round(total / gain_factor as f64, exposure_min, exposure_max);
+                (exposure, gain_factor)
+            }
+        };
+
+        for dependency in self.device.control_dependencies() {
+            let controls_exposure = dependency.dependents().iter().any(|dependent| {
+                matches!(
+                    dependent,
+                    KnownCameraControl::Exposure | KnownCameraControl::Gain
+                )
+            });
+            if controls_exposure && self.device.camera_control(dependency.parent()).is_ok() {
+                self.device
+                    .set_camera_control(dependency.parent(), dependency.manual_value().clone())?;
+            }
+        }
+        self.device.set_camera_control(
+            KnownCameraControl::Exposure,
+            ControlValueSetter::Integer(exposure),
+        )?;
+        if let Some((gain_min, _)) = gain_min {
+            self.device.set_camera_control(
+                KnownCameraControl::Gain,
+                ControlValueSetter::Integer(gain_min + gain_factor - 1),
+            )?;
+        }
+        self.frames_to_discard = self.settle_frames;
+
+        Ok(((exposure * gain_factor) as f32).log2())
+    }
+
+    /// Turns the camera's LED (privacy/in-use indicator) on or off.
+    /// # Errors
+    /// If the camera has no controllable LED, this will error with [`UnsupportedOperationError`](crate::NokhwaError::UnsupportedOperationError).
+    pub fn set_led(&mut self, on: bool) -> Result<(), NokhwaError> {
+        self.device.set_led(on)
+    }
+
+    /// Gets the sensor temperature in degrees Celsius. Returns `None` if the camera does not expose it.
+    #[must_use]
+    pub fn sensor_temperature(&self) -> Option<f32> {
+        self.device.sensor_temperature()
+    }
+
+    /// Gets the [`Colorimetry`] of the current format. Use [`Colorimetry::is_hdr()`] to check if frames are HDR. Returns `None` if the backend does not report it.
+    #[must_use]
+    pub fn colorimetry(&self) -> Option<Colorimetry> {
+        self.device.colorimetry()
+    }
+
+    /// Gets the readout line time and direction of the sensor's rolling shutter, e.g. to correct rolling shutter skew. Returns `None` if the driver does not expose them.
+    #[must_use]
+    pub fn rolling_shutter_info(&self) -> Option<RollingShutter> {
+        self.device.rolling_shutter_info()
+    }
+
+    /// Gets how the driver adjusted the requested format when the camera was opened, so e.g. "requested 60 FPS, got 30 FPS" can be shown to the user. See [`FormatNegotiation`].
+    #[must_use]
+    pub fn negotiated_vs_requested(&self) -> Option<FormatNegotiation> {
+        self.device.negotiated_vs_requested()
+    }
+
+    /// Hints the size in bytes of each frame buffer, to limit memory use on constrained devices. `None` lets the driver choose.
+    /// The driver may ignore the hint, see [`buffer_size()`](Self::buffer_size) for the size actually allocated.
+    /// # Errors
+    /// If the backend does not support this or renegotiating the format fails, this will error.
+    pub fn set_buffer_size_hint(&mut self, size: Option<u32>) -> Result<(), NokhwaError> {
+        self.device.set_buffer_size_hint(size)
+    }
+
+    /// Gets the size in bytes of each frame buffer allocated for the current format, if the backend reports it.
+    #[must_use]
+    pub fn buffer_size(&self) -> Option<u32> {
+        self.device.buffer_size()
+    }
+
+    /// Gets the speed of the USB connection the camera is plugged in with. Returns `None` if it is unknown or the camera is not a USB camera.
+    #[must_use]
+    pub fn usb_speed(&self) -> Option<UsbSpeed> {
+        self.device.usb_speed()
+    }
+
+    /// Resets the camera when it hangs in a way nothing else recovers it from, e.g. by re-enumerating it on the USB bus, then opens it again with the same format (and stream, if it was open).
+    /// The camera may come back under another [`CameraIndex`], which [`index()`](Self::index) is updated to.
+    /// # Errors
+    /// If the backend does not support this, the reset is not permitted, or the camera does not come back, this will error.
+    pub fn hard_reset(&mut self) -> Result<(), NokhwaError> {
+        self.device.hard_reset()?;
+        self.idx = self.device.camera_info().index().clone();
+        self.frames_to_discard = self.settle_frames;
+        Ok(())
     }
 
     /// Will open the camera stream with set parameters. This will be called internally if you try and call [`frame()`](CaptureBackendTrait::frame()) before you call [`open_stream()`](CaptureBackendTrait::open_stream()).
@@ -361,25 +1142,310 @@ impl Camera {
         self.device.is_stream_open()
     }
 
+    /// Opens the stream, counts the frames captured over `duration`, then stops the stream again, returning the measured frame rate.
+    ///
+    /// Use this to check that the current [`CameraFormat`] is actually sustainable (e.g. over USB 2.0) instead of trusting the frame rates the driver advertises.
+    /// # Errors
+    /// If the stream fails to open, a frame fails to be captured, or the stream fails to stop, this will error.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn measure_achievable_fps(&mut self, duration: Duration) -> Result<f32, NokhwaError> {
+        self.device.open_stream()?;
+        let start = Instant::now();
+        let mut frames_captured = 0_u32;
+        while start.elapsed() < duration {
+            if let Err(why) = self.device.frame_raw() {
+                self.device.stop_stream()?;
+                return Err(why);
+            }
+            frames_captured += 1;
+        }
+        let elapsed = start.elapsed();
+        self.device.stop_stream()?;
+
+        if elapsed.is_zero() {
+            return Ok(0_f32);
+        }
+        Ok(frames_captured as f32 / elapsed.as_secs_f32())
+    }
+
     /// Will get a frame from the camera as a Raw RGB image buffer. Depending on the backend, if you have not called [`open_stream()`](CaptureBackendTrait::open_stream()) before you called this,
     /// it will either return an error.
     /// # Errors
     /// If the backend fails to get the frame (e.g. already taken, busy, doesn't exist anymore), the decoding fails (e.g. MJPEG -> u8), or [`open_stream()`](CaptureBackendTrait::open_stream()) has not been called yet,
     /// this will error.
     pub fn frame(&mut self) -> Result<Buffer, NokhwaError> {
+        self.discard_settling_frames()?;
         self.device.frame()
     }
 
+    /// Same as [`frame()`](Self::frame), but gives up if no frame arrives within `timeout`.
+    /// # Errors
+    /// If no frame arrives in time, this returns a [`NokhwaError::TimeoutError`]. Backends that do not support timeouts return a `NotImplementedError`.
+    /// Otherwise, this errors like [`frame()`](Self::frame).
+    pub fn frame_timeout(&mut self, timeout: Duration) -> Result<Buffer, NokhwaError> {
+        self.discard_settling_frames()?;
+        self.device.frame_timeout(timeout)
+    }
+
+    /// Captures a frame and decodes it as a small RGB preview, at most `max_dim` pixels on its longest side. See [`Buffer::thumbnail()`].
+    /// # Errors
+    /// If the backend fails to get the frame, `max_dim` is 0, or the decoding fails, this will error.
+    pub fn thumbnail(
+        &mut self,
+        max_dim: u32,
+    ) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, NokhwaError> {
+        self.discard_settling_frames()?;
+        self.device.frame()?.thumbnail(max_dim)
+    }
+
+    /// Captures one frame at the camera's highest resolution, then returns to the previous [`CameraFormat`] and controls, e.g. for a high resolution snapshot while previewing at a low resolution.
+    ///
+    /// The largest format is picked by area, preferring the current [`FrameFormat`] and then the highest frame rate. The stream is restarted by the backend as needed,
+    /// and [`settle_frames()`](Self::settle_frames) frames are discarded at the new format before the snapshot is taken. If the stream was closed before, it is closed again.
+    /// # Errors
+    /// If the formats cannot be queried, a format cannot be set, the backend fails to get the frame, or the previous format or controls cannot be restored, this will error.
+    /// The previous format is restored even if the capture fails.
+    pub fn snapshot_hires(&mut self) -> Result<Buffer, NokhwaError> {
+        let previous = self.profile()?;
+        let was_open = self.device.is_stream_open();
+        let hires = self
+            .device
+            .compatible_camera_formats()?
+            .into_iter()
+            .max_by_key(|format| {
+                (
+                    u64::from(format.width()) * u64::from(format.height()),
+                    format.format() == previous.format().format(),
+                    format.frame_rate(),
+                )
+            })
+            .ok_or_else(|| NokhwaError::GetPropertyError {
+                property: "CameraFormat".to_string(),
+                error: "The camera reports no formats".to_string(),
+            })?;
+
+        let snapshot = self.capture_at_format(hires);
+        self.restore_configuration(&previous, was_open)?;
+        snapshot
+    }
+
+    // Switches to `format` and captures one frame once the settle frames have passed.
+    fn capture_at_format(&mut self, format: CameraFormat) -> Result<Buffer, NokhwaError> {
+        self.device.set_camera_format(format)?;
+        if !self.device.is_stream_open() {
+            self.device.open_stream()?;
+        }
+        self.frames_to_discard = self.settle_frames;
+        self.discard_settling_frames()?;
+        self.device.frame()
+    }
+
+    // Returns to the format, stream state, and control values saved in `previous`, after a snapshot or a failed configuration.
+    fn restore_configuration(
+        &mut self,
+        previous: &CameraProfile,
+        was_open: bool,
+    ) -> Result<(), NokhwaError> {
+        if self.device.camera_format() != previous.format() {
+            self.device.set_camera_format(previous.format())?;
+        }
+        if !was_open && self.device.is_stream_open() {
+            self.device.stop_stream()?;
+        }
+
+        // some drivers reset controls on a format change, so reapply whatever differs now, auto modes first
+        let parents = self
+            .device
+            .control_dependencies()
+            .iter()
+            .map(ControlDependency::parent)
+            .collect::<Vec<KnownCameraControl>>();
+        let mut changed = self.diff_controls(previous.controls());
+        changed.sort_by_key(|diff| !parents.contains(&diff.control()));
+        for diff in changed {
+            self.device
+                .set_camera_control(diff.control(), diff.saved().clone())?;
+        }
+        self.frames_to_discard = self.settle_frames;
+        Ok(())
+    }
+
+    /// Will get a frame from the camera as a [`Buffer`] that is guaranteed to own its data.
+    ///
+    /// The frame is copied out of the backend's (e.g. mmap) buffer exactly once, so the returned [`Buffer`] can be retained safely across captures.
+    /// [`frame()`](Self::frame) has the same semantics; this method exists to make the ownership explicit. If you want to avoid the copy, use [`frame_raw()`](Self::frame_raw).
+    /// # Errors
+    /// If the backend fails to get the frame (e.g. already taken, busy, doesn't exist anymore), or [`open_stream()`](CaptureBackendTrait::open_stream()) has not been called yet, this will error.
+    pub fn frame_owned(&mut self) -> Result<Buffer, NokhwaError> {
+        self.discard_settling_frames()?;
+        self.device.frame()
+    }
+
+    /// Gets the driver's capture timestamp of the last captured frame. Only differences between timestamps are meaningful. Returns `None` if the backend does not report it.
+    #[must_use]
+    pub fn frame_timestamp(&self) -> Option<Duration> {
+        self.device.frame_timestamp()
+    }
+
+    /// Gets the number of bytes in the last captured frame as filled by the driver, e.g. the size of a compressed MJPEG frame to monitor the bitrate.
+    /// Returns `None` if the backend does not report it or no frame was captured yet.
+    #[must_use]
+    pub fn last_frame_bytes(&self) -> Option<usize> {
+        self.device.last_frame_bytes()
+    }
+
+    /// Returns true if the last captured frame is a keyframe (I-frame) that can be decoded on its own, e.g. to start recording a compressed stream at a clean point.
+    /// Returns `None` if the backend does not report it or no frame was captured yet.
+    #[must_use]
+    pub fn frame_is_keyframe(&self) -> Option<bool> {
+        self.device.frame_is_keyframe()
+    }
+
+    /// Captures `n` consecutive frames as quickly as the driver delivers them, not paced to the frame rate.
+    ///
+    /// Each frame is returned with its capture time relative to the first frame of the burst. The driver's timestamps are used where available
+    /// (see [`frame_timestamp()`](CaptureBackendTrait::frame_timestamp)), otherwise the time the frame was received is used.
+    /// # Errors
+    /// If the backend fails to get a frame, or [`open_stream()`](CaptureBackendTrait::open_stream()) has not been called yet, this will error.
+    pub fn burst(&mut self, n: usize) -> Result<Vec<(Buffer, Duration)>, NokhwaError> {
+        self.discard_settling_frames()?;
+        let start = Instant::now();
+        let mut frames = Vec::with_capacity(n);
+        let mut first_timestamp = None;
+        for _ in 0..n {
+            let frame = self.device.frame()?;
+            let timestamp = self
+                .device
+                .frame_timestamp()
+                .unwrap_or_else(|| start.elapsed());
+            let first = *first_timestamp.get_or_insert(timestamp);
+            frames.push((frame, timestamp.saturating_sub(first)));
+        }
+        Ok(frames)
+    }
+
+    /// Will get one frame from the camera and cut each of `regions` out of it, returning each region's [`Buffer`] tagged with its index in `regions`.
+    ///
+    /// All regions come from the same sensor readout, so they may overlap and are captured at the same instant.
+    /// If `regions` is empty, the whole frame is returned as region `0`.
+    /// The regions are cropped in software (see [`Buffer::crop()`]): V4L2 has no standard way of configuring several hardware crop windows at once.
+    /// # Errors
+    /// If the backend fails to get the frame, or a region cannot be cropped (e.g. out of bounds, or the frame is MJPEG), this will error.
+    pub fn frame_regions(
+        &mut self,
+        regions: &[RegionOfInterest],
+    ) -> Result<Vec<(usize, Buffer)>, NokhwaError> {
+        self.discard_settling_frames()?;
+        let frame = self.device.frame()?;
+        if regions.is_empty() {
+            return Ok(vec![(0, frame)]);
+        }
+        regions
+            .iter()
+            .enumerate()
+            .map(|(index, region)| Ok((index, frame.crop(*region)?)))
+            .collect()
+    }
+
+    /// Captures `frames` frames into a YUV4MPEG2 (y4m) file at `path`, e.g. for piping into `ffmpeg`.
+    ///
+    /// The header is derived from the current [`CameraFormat`]. Frames are written as I420 (see [`Buffer::to_i420()`]):
+    /// YUV formats are repacked with MPEG-2 chroma siting, anything else (e.g. MJPEG, RGB) is converted with JPEG chroma siting.
+    /// # Errors
+    /// If the file cannot be written, the backend fails to get a frame, or [`open_stream()`](CaptureBackendTrait::open_stream()) has not been called yet, this will error.
+    pub fn record_y4m(&mut self, path: &Path, frames: usize) -> Result<(), NokhwaError> {
+        let write_error = |why: std::io::Error| {
+            NokhwaError::GeneralError(format!("Failed to write {}: {why}", path.display()))
+        };
+        let format = self.device.camera_format();
+        let chroma_siting = match format.format() {
+            FrameFormat::YUYV | FrameFormat::UYVY | FrameFormat::NV12 | FrameFormat::I420 => {
+                "420mpeg2"
+            }
+            _ => "420jpeg",
+        };
+
+        let (numerator, denominator) = format.frame_rate_ratio();
+
+        let mut file = BufWriter::new(File::create(path).map_err(write_error)?);
+        writeln!(
+            file,
+            "YUV4MPEG2 W{} H{} F{numerator}:{denominator} Ip A1:1 C{chroma_siting}",
+            format.width(),
+            format.height(),
+        )
+        .map_err(write_error)?;
+        self.discard_settling_frames()?;
+        for _ in 0..frames {
+            let planes = self.device.frame()?.to_i420()?;
+            file.write_all(b"FRAME\n").map_err(write_error)?;
+            file.write_all(&planes).map_err(write_error)?;
+        }
+        file.flush().map_err(write_error)
+    }
+
+    /// Captures `frames` MJPEG frames and muxes them into an AVI file in memory, with the current frame rate and an index, so it plays back in common players without `ffmpeg`.
+    /// # Errors
+    /// If the current format is not [`FrameFormat::MJPEG`], this will error with [`UnsupportedOperationError`](crate::NokhwaError::UnsupportedOperationError):
+    /// set an MJPEG format first, or convert the frames yourself (e.g. with [`record_y4m()`](Self::record_y4m)).
+    /// This will also error if the backend fails to get a frame, or [`open_stream()`](CaptureBackendTrait::open_stream()) has not been called yet.
+    pub fn record_mjpeg_avi(&mut self, frames: usize) -> Result<Vec<u8>, NokhwaError> {
+        let format = self.device.camera_format();
+        if format.format() != FrameFormat::MJPEG {
+            return Err(NokhwaError::UnsupportedOperationError(
+                self.device.backend(),
+            ));
+        }
+        self.discard_settling_frames()?;
+        let captured = (0..frames)
+            .map(|_| self.device.frame())
+            .collect::<Result<Vec<Buffer>, NokhwaError>>()?;
+        let frame_data = captured.iter().map(Buffer::buffer).collect::<Vec<&[u8]>>();
+        Ok(mux_mjpeg_avi(
+            &frame_data,
+            format.width(),
+            format.height(),
+            format.frame_rate_ratio(),
+        ))
+    }
+
+    /// Same as [`record_mjpeg_avi()`](Self::record_mjpeg_avi), but writes the AVI file to `path`.
+    /// # Errors
+    /// See [`record_mjpeg_avi()`](Self::record_mjpeg_avi). This will also error if the file cannot be written.
+    pub fn record_mjpeg_avi_to_file(
+        &mut self,
+        path: &Path,
+        frames: usize,
+    ) -> Result<(), NokhwaError> {
+        let avi = self.record_mjpeg_avi(frames)?;
+        std::fs::write(path, avi).map_err(|why| {
+            NokhwaError::GeneralError(format!("Failed to write {}: {why}", path.display()))
+        })
+    }
+
     /// Will get a frame from the camera **without** any processing applied, meaning you will usually get a frame you need to decode yourself.
+    ///
+    /// Depending on the backend, this may borrow directly from the driver's buffer, which is only valid until the next capture.
     /// # Errors
     /// If the backend fails to get the frame (e.g. already taken, busy, doesn't exist anymore), or [`open_stream()`](CaptureBackendTrait::open_stream()) has not been called yet, this will error.
     pub fn frame_raw(&mut self) -> Result<Cow<[u8]>, NokhwaError> {
+        self.discard_settling_frames()?;
         match self.device.frame_raw() {
             Ok(f) => Ok(f),
             Err(why) => Err(why),
         }
     }
 
+    /// Will get a frame from the camera **without** any processing applied, giving up if no frame arrives within `timeout`.
+    /// Use this for watchdog logic that needs to notice a camera that stopped delivering frames (e.g. unplugged mid-stream).
+    /// # Errors
+    /// If no frame arrives in time, this returns a [`NokhwaError::TimeoutError`]. Backends that do not support timeouts return a `NotImplementedError`.
+    /// Otherwise, this errors like [`frame_raw()`](Self::frame_raw).
+    pub fn frame_raw_timeout(&mut self, timeout: Duration) -> Result<Cow<[u8]>, NokhwaError> {
+        self.discard_settling_frames()?;
+        self.device.frame_raw_timeout(timeout)
+    }
+
     /// Directly writes the current frame into said `buffer`.
     /// # Errors
     /// If the backend fails to get the frame (e.g. already taken, busy, doesn't exist anymore), or [`open_stream()`](CaptureBackendTrait::open_stream()) has not been called yet, this will error.
@@ -387,6 +1453,7 @@ impl Camera {
         &mut self,
         buffer: &mut [u8],
     ) -> Result<(), NokhwaError> {
+        self.discard_settling_frames()?;
         self.device.frame()?.decode_image_to_buffer::<F>(buffer)
     }
 
@@ -401,6 +1468,7 @@ impl Camera {
         queue: &WgpuQueue,
         label: Option<&'a str>,
     ) -> Result<WgpuTexture, NokhwaError> {
+        self.discard_settling_frames()?;
         self.device.frame_texture(device, queue, label)
     }
 
@@ -418,6 +1486,29 @@ impl Drop for Camera {
     }
 }
 
+// The controls a `CameraProfile` saves: writable, active controls that hold a value. Buttons are left out, setting them would press them.
+fn profile_controls(controls: Vec<CameraControl>) -> Vec<(KnownCameraControl, ControlValueSetter)> {
+    controls
+        .into_iter()
+        .filter(|control| {
+            control.active()
+                && !matches!(
+                    control.description(),
+                    ControlValueDescription::None | ControlValueDescription::Unknown { .. }
+                )
+                && !control.flag().iter().any(|flag| {
+                    matches!(
+                        flag,
+                        KnownCameraControlFlag::ReadOnly
+                            | KnownCameraControlFlag::WriteOnly
+                            | KnownCameraControlFlag::Disabled
+                    )
+                })
+        })
+        .map(|control| (control.control(), control.value()))
+        .collect()
+}
+
 // TODO: Update as we go
 #[allow(clippy::ifs_same_cond)]
 fn figure_out_auto() -> Option<ApiBackend> {
@@ -569,3 +1660,72 @@ fn init_camera(
 
 #[cfg(feature = "output-threaded")]
 unsafe impl Send for Camera {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn control(
+        control: KnownCameraControl,
+        description: ControlValueDescription,
+        flag: Vec<KnownCameraControlFlag>,
+    ) -> CameraControl {
+        CameraControl::new(control, control.to_string(), description, flag, true)
+    }
+
+    #[test]
+    fn profile_holds_only_controls_with_a_value() {
+        let controls = vec![
+            control(
+                KnownCameraControl::Brightness,
+                ControlValueDescription::IntegerRange {
+                    min: 0,
+                    max: 255,
+                    value: 128,
+                    step: 1,
+                    default: 128,
+                },
+                vec![],
+            ),
+            control(
+                KnownCameraControl::Other(10_094_850),
+                ControlValueDescription::None,
+                vec![KnownCameraControlFlag::WriteOnly],
+            ),
+            control(
+                KnownCameraControl::Other(10_094_851),
+                ControlValueDescription::None,
+                vec![],
+            ),
+            control(
+                KnownCameraControl::Other(10_094_852),
+                ControlValueDescription::Unknown {
+                    kind: "Integer64".to_string(),
+                },
+                vec![],
+            ),
+            control(
+                KnownCameraControl::BacklightComp,
+                ControlValueDescription::Boolean {
+                    value: true,
+                    default: false,
+                },
+                vec![],
+            ),
+        ];
+
+        assert_eq!(
+            profile_controls(controls),
+            vec![
+                (
+                    KnownCameraControl::Brightness,
+                    ControlValueSetter::Integer(128)
+                ),
+                (
+                    KnownCameraControl::BacklightComp,
+                    ControlValueSetter::Boolean(true)
+                ),
+            ]
+        );
+    }
+}
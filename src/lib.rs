@@ -27,6 +27,8 @@
 //!
 //! Please read the README for more.
 
+mod auto_exposure;
+mod avi;
 /// Raw access to each of Nokhwa's backends.
 pub mod backends;
 mod camera;
@@ -38,6 +40,7 @@ pub mod js_camera;
 
 pub use nokhwa_core::pixel_format::FormatDecoder;
 mod query;
+mod sync_capture;
 /// A camera that runs in a different thread and can call your code based on callbacks.
 #[cfg(feature = "output-threaded")]
 #[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-threaded")))]
@@ -50,14 +53,16 @@ pub mod threaded;
 //     note = "please use `Camera` with `CameraIndex::String` and `input-opencv` enabled."
 // )]
 // pub use backends::capture::NetworkCamera;
+pub use auto_exposure::SoftwareAutoExposure;
 pub use camera::Camera;
 pub use init::*;
 pub use nokhwa_core::buffer::Buffer;
 pub use nokhwa_core::error::NokhwaError;
 pub use query::*;
+pub use sync_capture::SyncCaptureGroup;
 #[cfg(feature = "output-threaded")]
 #[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-threaded")))]
-pub use threaded::CallbackCamera;
+pub use threaded::{CallbackCamera, CallbackFlow, ConnectionState, ThreadPriority};
 
 pub mod utils {
     pub use nokhwa_core::types::*;
@@ -16,7 +16,7 @@
 
 use nokhwa_core::{
     error::NokhwaError,
-    types::{ApiBackend, CameraInfo},
+    types::{ApiBackend, CameraInfo, LogicalCamera},
 };
 
 /// Gets the native [`ApiBackend`]
@@ -38,6 +38,7 @@ pub fn native_api_backend() -> Option<ApiBackend> {
 /// - `AVFoundation`: The ID of the device is stored in the `misc` attribute of the [`CameraInfo`].
 /// - `AVFoundation`: There is lots of miscellaneous info in the `desc` attribute.
 /// - `WASM`: The `misc` field contains the device ID and group ID are seperated by a space (' ')
+/// - `Video4Linux`: Only video capture nodes are listed. [`CameraInfo::in_use()`] is probed by briefly allocating a capture buffer, which fails while another handle is streaming.
 /// # Errors
 /// If you use an unsupported API (check the README or crate root for more info), incompatible backend for current platform, incompatible platform, or insufficient permissions, etc
 /// this will error.
@@ -104,31 +105,171 @@ pub fn query(api: ApiBackend) -> Result<Vec<CameraInfo>, NokhwaError> {
     }
 }
 
+/// Same as [`query`], but for backends that touch each device node individually (`Video4Linux`), every device is queried on its own thread.
+/// This speeds up startup on systems with many cameras. Other backends fall back to [`query`].
+/// # Errors
+/// See [`query`].
+pub fn query_parallel(api: ApiBackend) -> Result<Vec<CameraInfo>, NokhwaError> {
+    match api {
+        ApiBackend::Video4Linux => query_v4l_parallel(),
+        ApiBackend::Auto
+            if std::env::consts::OS == "linux"
+                && cfg!(feature = "input-v4l")
+                && cfg!(target_os = "linux") =>
+        {
+            query_v4l_parallel()
+        }
+        _ => query(api),
+    }
+}
+
+/// Same as [`query`], but device nodes that belong to the same physical camera are grouped into one [`LogicalCamera`].
+///
+/// For `Video4Linux`, nodes are grouped by the bus they are attached to. The first node that can capture video is the [`primary()`](LogicalCamera::primary) one,
+/// so e.g. the `uvcvideo` metadata node or an ISP's processing nodes are not mistaken for separate cameras. Other backends return one [`LogicalCamera`] per device.
+/// # Errors
+/// See [`query`].
+pub fn query_logical(api: ApiBackend) -> Result<Vec<LogicalCamera>, NokhwaError> {
+    match api {
+        ApiBackend::Video4Linux => query_v4l_logical(),
+        ApiBackend::Auto
+            if std::env::consts::OS == "linux"
+                && cfg!(feature = "input-v4l")
+                && cfg!(target_os = "linux") =>
+        {
+            query_v4l_logical()
+        }
+        _ => Ok(query(api)?
+            .into_iter()
+            .map(|camera_info| LogicalCamera::new("", camera_info.clone(), vec![camera_info]))
+            .collect()),
+    }
+}
+
 // TODO: More
 
 #[cfg(all(feature = "input-v4l", target_os = "linux"))]
 #[allow(clippy::unnecessary_wraps)]
-#[allow(clippy::cast_possible_truncation)]
 fn query_v4l() -> Result<Vec<CameraInfo>, NokhwaError> {
-    use nokhwa_core::types::CameraIndex;
     Ok({
         let camera_info: Vec<CameraInfo> = v4l::context::enum_devices()
             .iter()
-            .map(|node| {
-                CameraInfo::new(
-                    &node
-                        .name()
-                        .unwrap_or(format!("{}", node.path().to_string_lossy())),
-                    &format!("Video4Linux Device @ {}", node.path().to_string_lossy()),
-                    "",
-                    CameraIndex::Index(node.index() as u32),
-                )
-            })
+            .filter(|node| v4l_node_is_capture(node))
+            .map(v4l_node_to_camera_info)
             .collect();
         camera_info
     })
 }
 
+#[cfg(all(feature = "input-v4l", target_os = "linux"))]
+#[allow(clippy::unnecessary_wraps)]
+fn query_v4l_parallel() -> Result<Vec<CameraInfo>, NokhwaError> {
+    let nodes = v4l::context::enum_devices();
+    // each node is opened on its own handle and never claimed (the in-use probe requests no buffers), so they can be queried side by side.
+    let camera_info = std::thread::scope(|scope| {
+        nodes
+            .iter()
+            .map(|node| {
+                scope
+                    .spawn(move || v4l_node_is_capture(node).then(|| v4l_node_to_camera_info(node)))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .filter_map(|handle| handle.join().ok().flatten())
+            .collect::<Vec<CameraInfo>>()
+    });
+    Ok(camera_info)
+}
+
+// Leaves out nodes that cannot capture video, e.g. the metadata node next to a UVC camera's capture node. Nodes that cannot be queried are kept.
+#[cfg(all(feature = "input-v4l", target_os = "linux"))]
+fn v4l_node_is_capture(node: &v4l::context::Node) -> bool {
+    use v4l::capability::Flags;
+    v4l::Device::with_path(node.path())
+        .and_then(|device| device.query_caps())
+        .map_or(true, |caps| {
+            caps.capabilities.contains(Flags::VIDEO_CAPTURE)
+        })
+}
+
+#[cfg(all(feature = "input-v4l", target_os = "linux"))]
+#[allow(clippy::cast_possible_truncation)]
+fn v4l_node_to_camera_info(node: &v4l::context::Node) -> CameraInfo {
+    use crate::backends::capture::{
+        associated_audio_device, device_in_use, device_node_path, V4L2_LOOPBACK_DRIVER,
+    };
+    use nokhwa_core::types::CameraIndex;
+    let mut camera_info = CameraInfo::new(
+        &node
+            .name()
+            .unwrap_or(format!("{}", node.path().to_string_lossy())),
+        &format!("Video4Linux Device @ {}", node.path().to_string_lossy()),
+        "",
+        CameraIndex::Index(node.index() as u32),
+    );
+    let is_virtual = v4l::Device::with_path(node.path())
+        .and_then(|device| device.query_caps())
+        .map(|caps| caps.driver == V4L2_LOOPBACK_DRIVER)
+        .unwrap_or(false);
+    camera_info.set_is_virtual(is_virtual);
+    let in_use = v4l::Device::with_path(node.path())
+        .map(|device| device_in_use(&device))
+        .unwrap_or(false);
+    camera_info.set_in_use(in_use);
+    camera_info.set_device_path(&device_node_path(node.path()).to_string_lossy());
+    if let Some(audio_device) = associated_audio_device(node.index() as u32) {
+        camera_info.set_associated_audio_device(&audio_device);
+    }
+    camera_info
+}
+
+#[cfg(all(feature = "input-v4l", target_os = "linux"))]
+#[allow(clippy::unnecessary_wraps)]
+fn query_v4l_logical() -> Result<Vec<LogicalCamera>, NokhwaError> {
+    use v4l::capability::Flags;
+    let mut nodes = v4l::context::enum_devices();
+    nodes.sort_by_key(v4l::context::Node::index);
+
+    let mut groups: Vec<(String, Vec<(CameraInfo, bool)>)> = vec![];
+    for node in &nodes {
+        let camera_info = v4l_node_to_camera_info(node);
+        let (bus, is_capture) =
+            match v4l::Device::with_path(node.path()).and_then(|device| device.query_caps()) {
+                Ok(caps) => (caps.bus, caps.capabilities.contains(Flags::VIDEO_CAPTURE)),
+                // unqueryable nodes are kept on their own
+                Err(_) => (node.path().to_string_lossy().to_string(), false),
+            };
+        match groups.iter_mut().find(|(group_bus, _)| *group_bus == bus) {
+            Some((_, group)) => group.push((camera_info, is_capture)),
+            None => groups.push((bus, vec![(camera_info, is_capture)])),
+        }
+    }
+
+    Ok(groups
+        .into_iter()
+        .map(|(bus, group)| {
+            let primary = group
+                .iter()
+                .find(|(_, is_capture)| *is_capture)
+                .unwrap_or(&group[0])
+                .0
+                .clone();
+            let nodes = group
+                .into_iter()
+                .map(|(camera_info, _)| camera_info)
+                .collect();
+            LogicalCamera::new(&bus, primary, nodes)
+        })
+        .collect())
+}
+
+#[cfg(any(not(feature = "input-v4l"), not(target_os = "linux")))]
+fn query_v4l_logical() -> Result<Vec<LogicalCamera>, NokhwaError> {
+    Err(NokhwaError::UnsupportedOperationError(
+        ApiBackend::Video4Linux,
+    ))
+}
+
 #[cfg(any(not(feature = "input-v4l"), not(target_os = "linux")))]
 fn query_v4l() -> Result<Vec<CameraInfo>, NokhwaError> {
     Err(NokhwaError::UnsupportedOperationError(
@@ -136,6 +277,13 @@ fn query_v4l() -> Result<Vec<CameraInfo>, NokhwaError> {
     ))
 }
 
+#[cfg(any(not(feature = "input-v4l"), not(target_os = "linux")))]
+fn query_v4l_parallel() -> Result<Vec<CameraInfo>, NokhwaError> {
+    Err(NokhwaError::UnsupportedOperationError(
+        ApiBackend::Video4Linux,
+    ))
+}
+
 #[cfg(feature = "input-uvc")]
 fn query_uvc() -> Result<Vec<CameraInfo>, NokhwaError> {
     use crate::CameraIndex;
@@ -0,0 +1,121 @@
+/*
+ * Copyright 2022 l1npengtul <l1npengtul@protonmail.com> / The Nokhwa Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::Camera;
+use nokhwa_core::{buffer::Buffer, error::NokhwaError};
+use std::time::{Duration, Instant};
+
+/// A group of cameras that are captured together, e.g. for stereo or multi-view setups.
+///
+/// This is a software best-effort synchronization: [`frame_all()`](SyncCaptureGroup::frame_all) takes one frame from each camera,
+/// then keeps replacing the oldest frame with a newer one until all frames lie within half a frame interval of each other.
+/// There is no hardware trigger involved.
+///
+/// Frames are matched by the driver's timestamps (see [`Camera::frame_timestamp()`]), which are only comparable between cameras on the same backend.
+/// Cameras that do not report timestamps use the time their frame was received instead.
+pub struct SyncCaptureGroup {
+    cameras: Vec<Camera>,
+    epoch: Instant,
+}
+
+impl SyncCaptureGroup {
+    /// Creates a new [`SyncCaptureGroup`] and opens the stream of every camera.
+    /// # Errors
+    /// If a stream fails to open, this will error.
+    pub fn new(mut cameras: Vec<Camera>) -> Result<Self, NokhwaError> {
+        for camera in &mut cameras {
+            if !camera.is_stream_open() {
+                camera.open_stream()?;
+            }
+        }
+        Ok(SyncCaptureGroup {
+            cameras,
+            epoch: Instant::now(),
+        })
+    }
+
+    /// Gets the cameras in this group, in the order they were given.
+    #[must_use]
+    pub fn cameras(&self) -> &[Camera] {
+        &self.cameras
+    }
+
+    /// Gets the cameras in this group mutably, e.g. to change controls.
+    pub fn cameras_mut(&mut self) -> &mut [Camera] {
+        &mut self.cameras
+    }
+
+    /// Gets the cameras back out of the group.
+    #[must_use]
+    pub fn into_cameras(self) -> Vec<Camera> {
+        self.cameras
+    }
+
+    // Captures one frame from camera `index`, with its timestamp.
+    fn capture(&mut self, index: usize) -> Result<(Buffer, Duration), NokhwaError> {
+        let camera = &mut self.cameras[index];
+        let frame = camera.frame()?;
+        let timestamp = camera
+            .frame_timestamp()
+            .unwrap_or_else(|| self.epoch.elapsed());
+        Ok((frame, timestamp))
+    }
+
+    /// Captures one frame from every camera, choosing the set of frames nearest to each other in time.
+    ///
+    /// Each frame is returned with the index of its camera and its timestamp. While trying to bring the set within half a frame interval (of the slowest camera),
+    /// at most as many extra frames as there are cameras are captured, so a camera that drifts too far is not waited on forever.
+    /// # Errors
+    /// If any camera fails to capture a frame, this will error.
+    pub fn frame_all(&mut self) -> Result<Vec<(usize, Buffer, Duration)>, NokhwaError> {
+        let mut frames = (0..self.cameras.len())
+            .map(|index| self.capture(index))
+            .collect::<Result<Vec<(Buffer, Duration)>, NokhwaError>>()?;
+
+        let slowest_frame_rate = self
+            .cameras
+            .iter()
+            .map(Camera::frame_rate)
+            .filter(|frame_rate| *frame_rate != 0)
+            .min()
+            .unwrap_or(30);
+        let tolerance = Duration::from_secs(1) / slowest_frame_rate / 2;
+        for _ in 0..self.cameras.len() {
+            let Some((oldest, _)) = frames
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, (_, timestamp))| *timestamp)
+            else {
+                break;
+            };
+            let newest = frames
+                .iter()
+                .map(|(_, timestamp)| *timestamp)
+                .max()
+                .unwrap_or_default();
+            if newest.saturating_sub(frames[oldest].1) <= tolerance {
+                break;
+            }
+            frames[oldest] = self.capture(oldest)?;
+        }
+
+        Ok(frames
+            .into_iter()
+            .enumerate()
+            .map(|(index, (frame, timestamp))| (index, frame, timestamp))
+            .collect())
+    }
+}
@@ -15,30 +15,127 @@
  */
 
 use crate::Camera;
+use image::{ImageBuffer, Rgb};
 use nokhwa_core::{
     buffer::Buffer,
     error::NokhwaError,
+    pixel_format::RgbFormat,
     types::{
         ApiBackend, CameraControl, CameraFormat, CameraIndex, CameraInfo, ControlValueSetter,
         FrameFormat, KnownCameraControl, RequestedFormat, Resolution,
     },
 };
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc, Mutex,
     },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
 };
+#[cfg(unix)]
+use std::{io, os::unix::thread::JoinHandleExt};
 
 type AtomicLock<T> = Arc<Mutex<T>>;
 pub type CallbackFn = fn(
     _camera: &Arc<Mutex<Camera>>,
-    _frame_callback: &Arc<Mutex<Option<Box<dyn FnMut(Buffer) + Send + 'static>>>>,
+    _frame_callback: &Arc<Mutex<Option<Box<dyn FnMut(Buffer) -> CallbackFlow + Send + 'static>>>>,
     _last_frame_captured: &Arc<Mutex<Buffer>>,
     _die_bool: &Arc<AtomicBool>,
 );
-type HeldCallbackType = Arc<Mutex<Box<dyn FnMut(Buffer) + Send + 'static>>>;
+type HeldCallbackType = Arc<Mutex<Box<dyn FnMut(Buffer) -> CallbackFlow + Send + 'static>>>;
+
+/// What the frame loop of a [`CallbackCamera`] should do after the callback returns, so a busy consumer can push back without an external channel.
+///
+/// Callbacks returning `()` are treated as [`CallbackFlow::Continue`].
+#[derive(Copy, Clone, Debug, Default, Hash, Ord, PartialOrd, Eq, PartialEq)]
+pub enum CallbackFlow {
+    /// Deliver the next frame as usual.
+    #[default]
+    Continue,
+    /// Drop the next `n` frames without calling the callback. They are still captured, so the driver's queue does not fill up with stale frames.
+    SkipNext(u32),
+    /// Stop the stream. The callback is called again once the stream is reopened with [`open_stream()`](CallbackCamera::open_stream).
+    Stop,
+}
+
+impl From<()> for CallbackFlow {
+    fn from((): ()) -> Self {
+        CallbackFlow::Continue
+    }
+}
+
+/// Whether the camera of a [`CallbackCamera`] is connected, see [`connection_state()`](CallbackCamera::connection_state).
+#[derive(Copy, Clone, Debug, Default, Hash, Ord, PartialOrd, Eq, PartialEq)]
+pub enum ConnectionState {
+    /// The camera is connected, frames are delivered as usual.
+    #[default]
+    Connected,
+    /// The camera was disconnected and the stream was stopped, as auto-reconnect is disabled.
+    Disconnected,
+    /// The camera was disconnected, the frame thread is trying to reopen it.
+    Reconnecting,
+}
+
+/// Scheduling priority of the frame thread of a [`CallbackCamera`], see [`set_thread_priority()`](CallbackCamera::set_thread_priority).
+#[derive(Copy, Clone, Debug, Default, Hash, Ord, PartialOrd, Eq, PartialEq)]
+pub enum ThreadPriority {
+    /// The default time-sharing scheduling of the OS.
+    #[default]
+    Normal,
+    /// Real-time FIFO scheduling (`SCHED_FIFO`) at the given priority, clamped to the range the OS allows (1 to 99 on Linux).
+    /// The frame thread then preempts every normal thread, which lowers the jitter between frames. On Linux this needs `CAP_SYS_NICE` or a high enough `RLIMIT_RTPRIO`.
+    RealTime(u8),
+}
+
+// How many of the most recent frames `measured_fps()` is averaged over.
+const FPS_WINDOW_FRAMES: usize = 30;
+
+// Frame statistics, updated for every frame captured by the frame thread or `poll_frame()`.
+#[derive(Default)]
+struct CaptureStats {
+    frames_captured: AtomicU64,
+    frames_dropped: AtomicU64,
+    // (receive time of each recent frame, sequence number of the last frame)
+    window: Mutex<(VecDeque<Instant>, Option<u32>)>,
+}
+
+impl CaptureStats {
+    fn record(&self, frame: &Buffer) {
+        self.frames_captured.fetch_add(1, Ordering::Relaxed);
+        let Ok(mut window) = self.window.lock() else {
+            return;
+        };
+        let (received, last_sequence) = &mut *window;
+        if received.len() == FPS_WINDOW_FRAMES {
+            received.pop_front();
+        }
+        received.push_back(Instant::now());
+        if let Some(sequence) = frame.sequence() {
+            // a sequence that goes backwards is a restarted stream, not a drop
+            if let Some(last) = last_sequence.filter(|last| sequence > *last) {
+                self.frames_dropped
+                    .fetch_add(u64::from(sequence - last - 1), Ordering::Relaxed);
+            }
+            *last_sequence = Some(sequence);
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn measured_fps(&self) -> f32 {
+        let Ok(window) = self.window.lock() else {
+            return 0.0;
+        };
+        let received = &window.0;
+        match (received.front(), received.back()) {
+            (Some(first), Some(last)) if last > first => {
+                (received.len() - 1) as f32 / last.duration_since(*first).as_secs_f32()
+            }
+            _ => 0.0,
+        }
+    }
+}
 
 /// Creates a camera that runs in a different thread that you can use a callback to access the frames of.
 /// It uses a `Arc` and a `Mutex` to ensure that this feels like a normal camera, but callback based.
@@ -46,7 +143,10 @@ type HeldCallbackType = Arc<Mutex<Box<dyn FnMut(Buffer) + Send + 'static>>>;
 ///
 /// Your function is called every time there is a new frame. In order to avoid frame loss, it should
 /// complete before a new frame is available. If you need to do heavy image processing, it may be
-/// beneficial to directly pipe the data to a new thread to process it there.
+/// beneficial to directly pipe the data to a new thread to process it there. It may also return a [`CallbackFlow`] to skip frames or stop the stream.
+///
+/// The frame thread is started by [`new()`](Self::new) and captures frames whenever the stream is open.
+/// **Note**: earlier versions never started it, so the callback was not called and frames only arrived through [`poll_frame()`](Self::poll_frame).
 ///
 /// Note that this does not have `WGPU` capabilities. However, it should be easy to implement.
 /// # SAFETY
@@ -57,31 +157,117 @@ pub struct CallbackCamera {
     camera: AtomicLock<Camera>,
     frame_callback: HeldCallbackType,
     last_frame_captured: AtomicLock<Buffer>,
+    stats: Arc<CaptureStats>,
+    connection_state: AtomicLock<ConnectionState>,
+    reconnect_interval: AtomicLock<Option<Duration>>,
     die_bool: Arc<AtomicBool>,
+    frame_thread: JoinHandle<()>,
 }
 
 impl CallbackCamera {
     /// Create a new `ThreadedCamera` from an `index` and `format`. `format` can be `None`.
+    ///
+    /// This starts the frame thread. It waits until the stream is opened with [`open_stream()`](Self::open_stream), then captures every frame and passes it to `callback`.
     /// # Errors
     /// This will error if you either have a bad platform configuration (e.g. `input-v4l` but not on linux) or the backend cannot create the camera (e.g. permission denied).
-    pub fn new(
+    pub fn new<R: Into<CallbackFlow>>(
         index: CameraIndex,
         format: RequestedFormat,
-        callback: impl FnMut(Buffer) + Send + 'static,
+        mut callback: impl FnMut(Buffer) -> R + Send + 'static,
     ) -> Result<Self, NokhwaError> {
         let arc_camera = Arc::new(Mutex::new(Camera::new(index, format)?));
+        let frame_callback: HeldCallbackType =
+            Arc::new(Mutex::new(Box::new(move |frame| callback(frame).into())));
+        let last_frame_captured = Arc::new(Mutex::new(Buffer::new(
+            Resolution::new(0, 0),
+            &vec![],
+            FrameFormat::GRAY,
+        )));
+        let stats = Arc::new(CaptureStats::default());
+        let connection_state = Arc::new(Mutex::new(ConnectionState::Connected));
+        let reconnect_interval = Arc::new(Mutex::new(None));
+        let die_bool = Arc::new(AtomicBool::default());
+
+        let frame_thread = {
+            let camera = arc_camera.clone();
+            let frame_callback = frame_callback.clone();
+            let last_frame_captured = last_frame_captured.clone();
+            let stats = stats.clone();
+            let connection_state = connection_state.clone();
+            let reconnect_interval = reconnect_interval.clone();
+            let die_bool = die_bool.clone();
+            thread::spawn(move || {
+                camera_frame_thread_loop(
+                    &camera,
+                    &frame_callback,
+                    &last_frame_captured,
+                    &stats,
+                    &connection_state,
+                    &reconnect_interval,
+                    &die_bool,
+                );
+            })
+        };
         Ok(CallbackCamera {
             camera: arc_camera,
-            frame_callback: Arc::new(Mutex::new(Box::new(callback))),
-            last_frame_captured: Arc::new(Mutex::new(Buffer::new(
-                Resolution::new(0, 0),
-                &vec![],
-                FrameFormat::GRAY,
-            ))),
-            die_bool: Arc::new(Default::default()),
+            frame_callback,
+            last_frame_captured,
+            stats,
+            connection_state,
+            reconnect_interval,
+            die_bool,
+            frame_thread,
         })
     }
 
+    /// Sets the scheduling priority of the thread that captures frames and calls the callback, e.g. [`ThreadPriority::RealTime`] for low-jitter capture
+    /// that is not preempted by other work. This can be changed at any time, also while streaming.
+    ///
+    /// Only the frame thread is affected, never the caller's thread. Keep the callback short when raising the priority, as it runs at the same priority.
+    /// # Errors
+    /// If the OS rejects the priority (e.g. missing permission for real-time scheduling), this will error. Setting a priority is only supported on Unix,
+    /// on other platforms this errors with [`NotImplementedError`](NokhwaError::NotImplementedError).
+    pub fn set_thread_priority(&self, priority: ThreadPriority) -> Result<(), NokhwaError> {
+        #[cfg(unix)]
+        {
+            let (policy, level) = match priority {
+                ThreadPriority::Normal => (libc::SCHED_OTHER, 0),
+                ThreadPriority::RealTime(level) => {
+                    // SAFETY: these only read the scheduler's limits for the policy.
+                    let (min, max) = unsafe {
+                        (
+                            libc::sched_get_priority_min(libc::SCHED_FIFO),
+                            libc::sched_get_priority_max(libc::SCHED_FIFO),
+                        )
+                    };
+                    (libc::SCHED_FIFO, libc::c_int::from(level).clamp(min, max))
+                }
+            };
+            // SAFETY: `sched_param` is plain data, zero is valid for its (platform specific) padding fields.
+            let mut param: libc::sched_param = unsafe { std::mem::zeroed() };
+            param.sched_priority = level;
+            // SAFETY: the thread handle is alive for as long as `self` holds its `JoinHandle`, and `param` outlives the call.
+            let result = unsafe {
+                libc::pthread_setschedparam(self.frame_thread.as_pthread_t(), policy, &param)
+            };
+            if result != 0 {
+                return Err(NokhwaError::SetPropertyError {
+                    property: "Thread Priority".to_string(),
+                    value: format!("{priority:?}"),
+                    error: io::Error::from_raw_os_error(result).to_string(),
+                });
+            }
+            Ok(())
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = (priority, &self.frame_thread);
+            Err(NokhwaError::NotImplementedError(
+                "Setting the frame thread priority is only supported on Unix".to_string(),
+            ))
+        }
+    }
+
     /// Gets the current Camera's index.
     pub fn index(&self) -> Result<&CameraIndex, NokhwaError> {
         Ok(self
@@ -408,9 +594,9 @@ impl CallbackCamera {
     }
 
     /// Sets the frame callback to the new specified function. This function will be called instead of the previous one(s).
-    pub fn set_callback(
+    pub fn set_callback<R: Into<CallbackFlow>>(
         &mut self,
-        callback: impl FnMut(Buffer) + Send + 'static,
+        mut callback: impl FnMut(Buffer) -> R + Send + 'static,
     ) -> Result<(), NokhwaError> {
         *self
             .frame_callback
@@ -418,11 +604,14 @@ impl CallbackCamera {
             .map_err(|why| NokhwaError::GetPropertyError {
                 property: "frame_callback".to_string(),
                 error: why.to_string(),
-            })? = Box::new(callback);
+            })? = Box::new(move |frame| callback(frame).into());
         Ok(())
     }
 
     /// Polls the camera for a frame, analogous to [`Camera::frame`](crate::Camera::frame)
+    ///
+    /// While the stream is open, the frame thread is capturing too. Each frame goes to either this call or the callback, never both,
+    /// so polling takes frames away from the callback. To look at frames without taking them, use [`latest_frame()`](Self::latest_frame) or [`last_frame()`](Self::last_frame).
     /// # Errors
     /// This will error if the camera fails to capture a frame.
     pub fn poll_frame(&mut self) -> Result<Buffer, NokhwaError> {
@@ -431,6 +620,7 @@ impl CallbackCamera {
             .lock()
             .map_err(|why| NokhwaError::ReadFrameError(why.to_string()))?
             .frame()?;
+        self.stats.record(&frame);
         *self
             .last_frame_captured
             .lock()
@@ -447,6 +637,70 @@ impl CallbackCamera {
             .clone())
     }
 
+    /// Decodes the most recent frame captured (by the frame thread or [`poll_frame()`](Self::poll_frame)) to RGB, e.g. for a render loop that pulls frames on its own schedule.
+    ///
+    /// This is the RGB "last frame" accessor that runs alongside the callback. [`poll_frame()`](Self::poll_frame) and [`last_frame()`](Self::last_frame) keep returning
+    /// the raw [`Buffer`], so they are not changed to return RGB.
+    ///
+    /// This does not consume anything, so the callback still gets every frame. Intermediate frames are not queued: if this is called faster than the capture rate,
+    /// **the same frame is returned again**. Returns `None` if no frame has been captured yet or the frame fails to decode.
+    #[must_use]
+    pub fn latest_frame(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        let frame = self.last_frame_captured.lock().ok()?.clone();
+        if frame.buffer().is_empty() {
+            return None;
+        }
+        frame.decode_image::<RgbFormat>().ok()
+    }
+
+    /// Gets the number of frames captured since this camera was created.
+    #[must_use]
+    pub fn frames_captured(&self) -> u64 {
+        self.stats.frames_captured.load(Ordering::Relaxed)
+    }
+
+    /// Gets the number of frames the driver dropped between captures, estimated from gaps in the frame sequence numbers (see [`Buffer::sequence()`]).
+    /// This stays `0` on backends that do not report sequence numbers.
+    #[must_use]
+    pub fn frames_dropped(&self) -> u64 {
+        self.stats.frames_dropped.load(Ordering::Relaxed)
+    }
+
+    /// Gets the frame rate actually achieved over the last 30 frames, compared to the configured [`frame_rate()`](Self::frame_rate).
+    /// Returns `0.0` until at least two frames have been captured.
+    #[must_use]
+    pub fn measured_fps(&self) -> f32 {
+        self.stats.measured_fps()
+    }
+
+    /// Enables reconnecting when the camera is disconnected (e.g. briefly unplugged), trying to reopen it every `interval`, or disables it with `None` (the default).
+    /// Once the camera is back, its stream is reopened and the callback resumes. See [`Camera::reconnect()`] for how the camera is found again.
+    ///
+    /// Without auto-reconnect, the stream is stopped when the camera is disconnected.
+    /// # Errors
+    /// If the setting is poisoned (the frame thread panicked), this will error.
+    pub fn set_auto_reconnect(&mut self, interval: Option<Duration>) -> Result<(), NokhwaError> {
+        *self
+            .reconnect_interval
+            .lock()
+            .map_err(|why| NokhwaError::SetPropertyError {
+                property: "Auto Reconnect".to_string(),
+                value: format!("{interval:?}"),
+                error: why.to_string(),
+            })? = interval;
+        Ok(())
+    }
+
+    /// Gets whether the camera is connected, or being reconnected (see [`set_auto_reconnect()`](Self::set_auto_reconnect)).
+    ///
+    /// Only backends that report [`DeviceDisconnected`](NokhwaError::DeviceDisconnected) (currently V4L2) can leave [`ConnectionState::Connected`].
+    #[must_use]
+    pub fn connection_state(&self) -> ConnectionState {
+        self.connection_state
+            .lock()
+            .map_or(ConnectionState::Connected, |state| *state)
+    }
+
     /// Checks if stream if open. If it is, it will return true.
     pub fn is_stream_open(&self) -> Result<bool, NokhwaError> {
         Ok(self
@@ -477,25 +731,111 @@ impl Drop for CallbackCamera {
     }
 }
 
+// How long the frame loop waits before trying again while the stream is closed or capturing fails, instead of spinning on failing `frame()` calls.
+const FRAME_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+// How long the frame loop waits for a frame while holding the camera lock. Bounding the wait releases the lock (and rechecks `die_bool`) even when no frames arrive.
+const FRAME_POLL_TIMEOUT: Duration = Duration::from_millis(100);
+
 fn camera_frame_thread_loop(
     camera: &AtomicLock<Camera>,
     frame_callback: &HeldCallbackType,
     last_frame_captured: &AtomicLock<Buffer>,
+    stats: &CaptureStats,
+    connection_state: &AtomicLock<ConnectionState>,
+    reconnect_interval: &AtomicLock<Option<Duration>>,
     die_bool: &Arc<AtomicBool>,
 ) {
-    loop {
-        if let Ok(mut camera) = camera.lock() {
-            if let Ok(frame) = camera.frame() {
-                if let Ok(last_frame) = last_frame_captured.lock() {
-                    *last_frame = frame.clone();
-                    if let Ok(cb) = frame_callback.lock() {
-                        cb(frame);
-                    }
+    let mut frames_to_skip = 0;
+    while !die_bool.load(Ordering::SeqCst) {
+        let frame = match camera.lock() {
+            Ok(mut camera) if camera.is_stream_open() => {
+                // backends without a bounded dequeue block until the next frame
+                match camera.frame_timeout(FRAME_POLL_TIMEOUT) {
+                    Err(NokhwaError::NotImplementedError(_)) => camera.frame(),
+                    frame => frame,
                 }
             }
+            _ => {
+                thread::sleep(FRAME_RETRY_INTERVAL);
+                continue;
+            }
+        };
+        let frame = match frame {
+            Ok(frame) => frame,
+            // the lock is released by now, give waiting callers a chance to take it
+            Err(NokhwaError::TimeoutError(_)) => {
+                thread::yield_now();
+                continue;
+            }
+            Err(NokhwaError::DeviceDisconnected(_)) => {
+                reconnect_camera(camera, connection_state, reconnect_interval, die_bool);
+                continue;
+            }
+            Err(_) => {
+                thread::sleep(FRAME_RETRY_INTERVAL);
+                continue;
+            }
+        };
+        if let Ok(mut state) = connection_state.lock() {
+            *state = ConnectionState::Connected;
+        }
+        stats.record(&frame);
+        if let Ok(mut last_frame) = last_frame_captured.lock() {
+            *last_frame = frame.clone();
+        }
+        if frames_to_skip > 0 {
+            frames_to_skip -= 1;
+            continue;
+        }
+        let flow = match frame_callback.lock() {
+            Ok(mut cb) => cb(frame),
+            Err(_) => CallbackFlow::Continue,
+        };
+        match flow {
+            CallbackFlow::Continue => {}
+            CallbackFlow::SkipNext(n) => frames_to_skip = n,
+            CallbackFlow::Stop => {
+                if let Ok(mut camera) = camera.lock() {
+                    let _stop_stream_err = camera.stop_stream();
+                }
+            }
+        }
+    }
+}
+
+// Tries to reopen a disconnected camera every `reconnect_interval` until it is back, auto-reconnect is disabled, or the camera is dropped.
+// Without auto-reconnect the stream is stopped, so the frame loop idles instead of failing on the dead device.
+fn reconnect_camera(
+    camera: &AtomicLock<Camera>,
+    connection_state: &AtomicLock<ConnectionState>,
+    reconnect_interval: &AtomicLock<Option<Duration>>,
+    die_bool: &Arc<AtomicBool>,
+) {
+    let set_state = |new_state| {
+        if let Ok(mut state) = connection_state.lock() {
+            *state = new_state;
         }
-        if die_bool.load(Ordering::SeqCst) {
-            break;
+    };
+    while !die_bool.load(Ordering::SeqCst) {
+        let Some(interval) = reconnect_interval
+            .lock()
+            .ok()
+            .and_then(|interval| *interval)
+        else {
+            set_state(ConnectionState::Disconnected);
+            if let Ok(mut camera) = camera.lock() {
+                let _stop_stream_err = camera.stop_stream();
+            }
+            return;
+        };
+        set_state(ConnectionState::Reconnecting);
+        thread::sleep(interval);
+        if let Ok(mut camera) = camera.lock() {
+            if camera.reconnect().is_ok() {
+                set_state(ConnectionState::Connected);
+                return;
+            }
         }
     }
 }